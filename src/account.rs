@@ -1,18 +1,49 @@
+use secrecy::SecretString;
+
+/// Credentials of a Jobcan account.
+///
+/// The password is zeroized on drop and redacted in `Debug`.
+#[derive(Debug)]
 pub struct Account {
     email: String,
-    password: String,
+    password: SecretString,
 }
 
 impl Account {
-    pub fn new(email: String, password: String) -> Account {
-        Account { email, password }
+    pub fn new(email: String, password: impl Into<SecretString>) -> Account {
+        Account {
+            email,
+            password: password.into(),
+        }
     }
 
     pub fn email(&self) -> &str {
         &self.email
     }
 
-    pub fn password(&self) -> &str {
+    /// Use [`secrecy::ExposeSecret::expose_secret`] to read the password.
+    pub fn password(&self) -> &SecretString {
         &self.password
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use secrecy::ExposeSecret;
+
+    use super::*;
+
+    #[test]
+    fn debug_redacts_password() {
+        // Arrange
+        let account = Account::new("user@example.com".into(), "secret".to_string());
+
+        // Act
+        let debug = format!("{:?}", account);
+
+        // Assert
+        assert!(debug.contains("user@example.com"));
+        assert!(!debug.contains("secret"));
+        assert!(account.password().expose_secret() == "secret");
+    }
+}
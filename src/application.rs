@@ -0,0 +1,100 @@
+use std::fmt::Display;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use serde::Serialize;
+
+use crate::approval_state::ApprovalState;
+
+/// Kinds of application (申請) listed in the application history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApplicationKind {
+    /// Stamp correction (打刻修正申請).
+    Correction,
+    /// Leave (休暇申請).
+    Leave,
+    /// Overtime (残業申請).
+    Overtime,
+    /// Any other application, e.g. a custom workflow of the company.
+    Other,
+}
+
+impl Display for ApplicationKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ApplicationKind::Correction => "Correction",
+            ApplicationKind::Leave => "Leave",
+            ApplicationKind::Overtime => "Overtime",
+            ApplicationKind::Other => "Other",
+        };
+        f.pad(s)
+    }
+}
+
+impl ApplicationKind {
+    /// Classify the label Jobcan shows for an application, e.g. `打刻修正申請`.
+    pub fn from_label(label: &str) -> ApplicationKind {
+        if label.contains("打刻") {
+            ApplicationKind::Correction
+        } else if label.contains("休暇") {
+            ApplicationKind::Leave
+        } else if label.contains("残業") {
+            ApplicationKind::Overtime
+        } else {
+            ApplicationKind::Other
+        }
+    }
+}
+
+/// An application in the history of the employee, with its state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Application {
+    pub(crate) kind: ApplicationKind,
+    pub(crate) label: String,
+    pub(crate) date: NaiveDate,
+    pub(crate) submitted_at: Option<NaiveDateTime>,
+    pub(crate) approver: Option<String>,
+    pub(crate) state: ApprovalState,
+}
+
+impl Application {
+    pub fn kind(&self) -> ApplicationKind {
+        self.kind
+    }
+
+    /// Type of the application as Jobcan shows it, e.g. `休暇申請`.
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    /// Date the application is for.
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn submitted_at(&self) -> Option<NaiveDateTime> {
+        self.submitted_at
+    }
+
+    pub fn approver(&self) -> Option<&str> {
+        self.approver.as_deref()
+    }
+
+    pub fn state(&self) -> ApprovalState {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kind_from_labels() {
+        // Assert
+        assert!(ApplicationKind::from_label("打刻修正申請") == ApplicationKind::Correction);
+        assert!(ApplicationKind::from_label("休暇申請") == ApplicationKind::Leave);
+        assert!(ApplicationKind::from_label("残業申請") == ApplicationKind::Overtime);
+        assert!(ApplicationKind::from_label("経費申請") == ApplicationKind::Other);
+    }
+}
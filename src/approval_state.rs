@@ -0,0 +1,62 @@
+use std::fmt::Display;
+
+use serde::Serialize;
+
+/// State of an application (申請) such as a correction or leave request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalState {
+    /// Recorded as is, since the company doesn't require approval.
+    Applied,
+    Pending,
+    Approved,
+    Rejected,
+    /// Withdrawn by the applicant.
+    Canceled,
+}
+
+impl Display for ApprovalState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            ApprovalState::Applied => "Applied",
+            ApprovalState::Pending => "Pending approval",
+            ApprovalState::Approved => "Approved",
+            ApprovalState::Rejected => "Rejected",
+            ApprovalState::Canceled => "Canceled",
+        };
+        f.pad(s)
+    }
+}
+
+impl ApprovalState {
+    /// Parse the label Jobcan shows for an application, e.g. `申請中`. An entry without a label
+    /// needs no approval.
+    pub fn from_label(label: Option<&str>) -> Option<ApprovalState> {
+        match label {
+            None => Some(ApprovalState::Applied),
+            Some("申請中" | "承認待ち") => Some(ApprovalState::Pending),
+            Some("承認済" | "承認済み") => Some(ApprovalState::Approved),
+            Some("却下" | "差し戻し" | "差戻し") => Some(ApprovalState::Rejected),
+            Some("取消" | "取り消し" | "取下げ" | "取り下げ") => {
+                Some(ApprovalState::Canceled)
+            }
+            Some(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_from_labels() {
+        // Assert
+        assert!(ApprovalState::from_label(None) == Some(ApprovalState::Applied));
+        assert!(ApprovalState::from_label(Some("申請中")) == Some(ApprovalState::Pending));
+        assert!(ApprovalState::from_label(Some("承認済")) == Some(ApprovalState::Approved));
+        assert!(ApprovalState::from_label(Some("却下")) == Some(ApprovalState::Rejected));
+        assert!(ApprovalState::from_label(Some("取消")) == Some(ApprovalState::Canceled));
+        assert!(ApprovalState::from_label(Some("保留")).is_none());
+    }
+}
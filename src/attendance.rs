@@ -0,0 +1,53 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// A day of the attendance record (出勤簿).
+///
+/// Times and durations are kept as Jobcan shows them (e.g. `09:02`, `25:30`, `8:00`),
+/// since a night shift can end after 24:00.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AttendanceRecord {
+    pub(crate) date: NaiveDate,
+    pub(crate) holiday_type: Option<String>,
+    pub(crate) clock_in: Option<String>,
+    pub(crate) clock_out: Option<String>,
+    pub(crate) break_time: Option<String>,
+    pub(crate) work_time: Option<String>,
+    pub(crate) overtime: Option<String>,
+    pub(crate) notes: Option<String>,
+}
+
+impl AttendanceRecord {
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Kind of day off (休日区分), e.g. `法定休日`.
+    pub fn holiday_type(&self) -> Option<&str> {
+        self.holiday_type.as_deref()
+    }
+
+    pub fn clock_in(&self) -> Option<&str> {
+        self.clock_in.as_deref()
+    }
+
+    pub fn clock_out(&self) -> Option<&str> {
+        self.clock_out.as_deref()
+    }
+
+    pub fn break_time(&self) -> Option<&str> {
+        self.break_time.as_deref()
+    }
+
+    pub fn work_time(&self) -> Option<&str> {
+        self.work_time.as_deref()
+    }
+
+    pub fn overtime(&self) -> Option<&str> {
+        self.overtime.as_deref()
+    }
+
+    pub fn notes(&self) -> Option<&str> {
+        self.notes.as_deref()
+    }
+}
@@ -0,0 +1,433 @@
+use std::{collections::BTreeMap, fs, path::PathBuf};
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+use serde::Deserialize;
+
+use crate::japanese_holidays;
+
+/// Non-working days, the `[calendar]` table of the config file.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CalendarConfig {
+    /// Whether Japanese public holidays are days off. On by default.
+    #[serde(default = "default_japanese_holidays")]
+    pub japanese_holidays: bool,
+    /// Company calendars in iCalendar (`.ics`) or CSV (`.csv`) format.
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+}
+
+impl Default for CalendarConfig {
+    fn default() -> Self {
+        CalendarConfig {
+            japanese_holidays: default_japanese_holidays(),
+            files: Vec::new(),
+        }
+    }
+}
+
+fn default_japanese_holidays() -> bool {
+    true
+}
+
+/// Days off from Japanese public holidays and company calendars.
+#[derive(Debug, Default)]
+pub struct Calendar {
+    japanese_holidays: bool,
+    days_off: BTreeMap<NaiveDate, String>,
+}
+
+impl Calendar {
+    pub fn load(config: &CalendarConfig) -> Result<Calendar, String> {
+        let mut calendar = Calendar {
+            japanese_holidays: config.japanese_holidays,
+            days_off: BTreeMap::new(),
+        };
+        for path in &config.files {
+            let text = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            let days_off = match path.extension().and_then(|e| e.to_str()) {
+                Some("ics") => parse_ics(&text),
+                Some("csv") => parse_csv(&text),
+                _ => Err("The extension must be .ics or .csv".to_string()),
+            }
+            .map_err(|e| format!("Invalid calendar {}: {}", path.display(), e))?;
+            calendar.days_off.extend(days_off);
+        }
+        Ok(calendar)
+    }
+
+    /// Name of the day off on `date`. Company calendars take precedence over public holidays.
+    pub fn holiday(&self, date: NaiveDate) -> Option<&str> {
+        self.days_off.get(&date).map(String::as_str).or_else(|| {
+            self.japanese_holidays
+                .then(|| japanese_holidays::holiday(date))
+                .flatten()
+        })
+    }
+}
+
+/// A `VEVENT` of an iCalendar file being parsed.
+#[derive(Default)]
+struct IcsEvent {
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    summary: String,
+    rule: Option<String>,
+    excluded: Vec<NaiveDate>,
+}
+
+/// Years a recurring event without `COUNT` or `UNTIL` is expanded for.
+const RECURRENCE_YEARS: i32 = 50;
+
+/// All-day events of an iCalendar file, e.g. an export of Google Calendar. Recurring events
+/// are expanded if they repeat yearly or weekly, and fail otherwise.
+fn parse_ics(text: &str) -> Result<Vec<(NaiveDate, String)>, String> {
+    // Note: A line starting with a space or a tab continues the previous one
+    let unfolded = text
+        .replace("\r\n", "\n")
+        .replace("\n ", "")
+        .replace("\n\t", "");
+
+    let mut days_off = Vec::new();
+    let mut event: Option<IcsEvent> = None;
+    for line in unfolded.lines() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        // Note: Parameters such as `;VALUE=DATE` follow the property name
+        let (property, parameters) = name.split_once(';').unwrap_or((name, ""));
+        match (property, event.as_mut()) {
+            ("BEGIN", None) if value == "VEVENT" => event = Some(IcsEvent::default()),
+            ("DTSTART", Some(event)) => event.start = Some(ics_date(value)?),
+            ("DTEND", Some(event)) => {
+                let date = ics_date(value)?;
+                // Note: The end of an all-day event is exclusive, unlike that of a timed one
+                let all_day = parameters.contains("VALUE=DATE") || value.len() == 8;
+                event.end = Some(if all_day {
+                    date.pred_opt().unwrap_or(date)
+                } else {
+                    date
+                });
+            }
+            ("SUMMARY", Some(event)) => event.summary = ics_text(value),
+            ("RRULE", Some(event)) => event.rule = Some(value.to_string()),
+            ("EXDATE", Some(event)) => {
+                for value in value.split(',') {
+                    event.excluded.push(ics_date(value)?);
+                }
+            }
+            ("END", Some(_)) if value == "VEVENT" => {
+                let event = event.take().unwrap();
+                let start = event.start.ok_or("DTSTART is missing in VEVENT")?;
+                let end = event.end.unwrap_or(start).max(start);
+                let days = (end - start).num_days() as u64;
+                let starts = match &event.rule {
+                    Some(rule) => ics_occurrences(start, rule).ok_or_else(|| {
+                        format!(
+                            "Recurring event `{}` has an unsupported RRULE `{}`. Only FREQ=YEARLY and FREQ=WEEKLY are supported.",
+                            event.summary, rule
+                        )
+                    })?,
+                    None => vec![start],
+                };
+                days_off.extend(
+                    starts
+                        .into_iter()
+                        .filter(|start| !event.excluded.contains(start))
+                        .flat_map(|start| {
+                            (0..=days).filter_map(move |i| start.checked_add_days(Days::new(i)))
+                        })
+                        .map(|date| (date, event.summary.clone())),
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok(days_off)
+}
+
+/// Starts of the occurrences of an event starting on `start` and repeated by `rule`, e.g.
+/// `FREQ=WEEKLY;BYDAY=MO,FR;UNTIL=20241231`. Only yearly rules on the date of `start` and
+/// weekly rules are supported, with `INTERVAL`, `COUNT` and `UNTIL`.
+fn ics_occurrences(start: NaiveDate, rule: &str) -> Option<Vec<NaiveDate>> {
+    let mut freq = None;
+    let mut interval = 1;
+    let mut count = None;
+    let mut until = NaiveDate::from_ymd_opt(start.year() + RECURRENCE_YEARS, 12, 31)?;
+    let mut weekdays = None;
+    for part in rule.split(';') {
+        let (name, value) = part.split_once('=')?;
+        match name {
+            "FREQ" => freq = Some(value),
+            "INTERVAL" => interval = value.parse().ok().filter(|&n: &u32| n > 0)?,
+            "COUNT" => count = Some(value.parse::<usize>().ok()?),
+            "UNTIL" => until = ics_date(value).ok()?,
+            "WKST" => {}
+            // Note: Exports give the month and day of a yearly event even though they are
+            // those of the start
+            "BYMONTH" if value.parse() == Ok(start.month()) => {}
+            "BYMONTHDAY" if value.parse() == Ok(start.day()) => {}
+            "BYDAY" => {
+                weekdays = Some(
+                    value
+                        .split(',')
+                        .map(ics_weekday)
+                        .collect::<Option<Vec<_>>>()?,
+                );
+            }
+            _ => return None,
+        }
+    }
+
+    let mut starts: Vec<NaiveDate> = match (freq?, weekdays) {
+        ("YEARLY", None) => (0..=RECURRENCE_YEARS)
+            .step_by(interval as usize)
+            .filter_map(|years| start.with_year(start.year() + years))
+            .collect(),
+        ("WEEKLY", weekdays) => {
+            let weekdays = weekdays.unwrap_or_else(|| vec![start.weekday()]);
+            let week = start.week(Weekday::Mon).first_day();
+            let weeks = RECURRENCE_YEARS as u64 * 53;
+            (0..=weeks)
+                .step_by(interval as usize)
+                .filter_map(|i| week.checked_add_days(Days::new(i * 7)))
+                .flat_map(|monday| {
+                    let mut days: Vec<NaiveDate> = weekdays
+                        .iter()
+                        .filter_map(|weekday| {
+                            monday
+                                .checked_add_days(Days::new(weekday.num_days_from_monday().into()))
+                        })
+                        .collect();
+                    days.sort();
+                    days
+                })
+                .filter(|date| *date >= start)
+                .collect()
+        }
+        _ => return None,
+    };
+    starts.retain(|date| *date <= until);
+    if let Some(count) = count {
+        starts.truncate(count);
+    }
+    Some(starts)
+}
+
+/// Weekday of a `BYDAY` value without an ordinal, e.g. `MO`.
+fn ics_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Mon),
+        "TU" => Some(Weekday::Tue),
+        "WE" => Some(Weekday::Wed),
+        "TH" => Some(Weekday::Thu),
+        "FR" => Some(Weekday::Fri),
+        "SA" => Some(Weekday::Sat),
+        "SU" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Date of a `DATE` or `DATE-TIME` value, e.g. `20240101` or `20240101T090000Z`.
+fn ics_date(value: &str) -> Result<NaiveDate, String> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y%m%d").ok())
+        .ok_or_else(|| format!("Invalid date `{}`", value))
+}
+
+fn ics_text(value: &str) -> String {
+    value
+        .replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+/// Rows of `date,name` with an optional header row, e.g. `2024-12-30,Year-end holiday`. The
+/// name is optional.
+fn parse_csv(text: &str) -> Result<Vec<(NaiveDate, String)>, String> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .comment(Some(b'#'))
+        .from_reader(text.as_bytes());
+
+    let mut days_off = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record.map_err(|e| e.to_string())?;
+        let field = record.get(0).unwrap_or_default().trim();
+        let date = NaiveDate::parse_from_str(field, "%Y-%m-%d")
+            .or_else(|_| NaiveDate::parse_from_str(field, "%Y/%m/%d"));
+        match date {
+            Ok(date) => {
+                let name = record.get(1).unwrap_or_default().trim();
+                let name = if name.is_empty() { "Day off" } else { name };
+                days_off.push((date, name.to_string()));
+            }
+            Err(_) if i == 0 => {} // Note: The header row
+            Err(_) => {
+                let line = record.position().map_or(i as u64 + 1, |p| p.line());
+                return Err(format!("Invalid date `{}` at line {}", field, line));
+            }
+        }
+    }
+    Ok(days_off)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(text: &str) -> NaiveDate {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn parse_ics_with_all_day_and_timed_events() {
+        // Arrange
+        let text = "BEGIN:VCALENDAR\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART;VALUE=DATE:20241230\r\n\
+            DTEND;VALUE=DATE:20250101\r\n\
+            SUMMARY:Year-end\\, closed\r\n\
+            END:VEVENT\r\n\
+            BEGIN:VEVENT\r\n\
+            DTSTART:20240801T000000Z\r\n\
+            DTEND:20240801T235959Z\r\n\
+            SUMMARY:Founda\r\n tion day\r\n\
+            END:VEVENT\r\n\
+            END:VCALENDAR\r\n";
+
+        // Act
+        let days_off = parse_ics(text).unwrap();
+
+        // Assert
+        assert!(
+            days_off
+                == vec![
+                    (date("2024-12-30"), "Year-end, closed".to_string()),
+                    (date("2024-12-31"), "Year-end, closed".to_string()),
+                    (date("2024-08-01"), "Foundation day".to_string()),
+                ]
+        );
+    }
+
+    #[test]
+    fn parse_ics_with_yearly_event() {
+        // Arrange
+        let text = "BEGIN:VEVENT\n\
+            DTSTART;VALUE=DATE:20220801\n\
+            DTEND;VALUE=DATE:20220802\n\
+            RRULE:FREQ=YEARLY;BYMONTH=8;BYMONTHDAY=1;COUNT=4\n\
+            EXDATE;VALUE=DATE:20230801\n\
+            SUMMARY:Foundation day\n\
+            END:VEVENT\n";
+
+        // Act
+        let days_off = parse_ics(text).unwrap();
+
+        // Assert
+        let dates: Vec<NaiveDate> = days_off.iter().map(|(date, _)| *date).collect();
+        assert!(dates == [date("2022-08-01"), date("2024-08-01"), date("2025-08-01")]);
+    }
+
+    #[test]
+    fn parse_ics_with_weekly_event() {
+        // Arrange
+        let text = "BEGIN:VEVENT\n\
+            DTSTART;VALUE=DATE:20240703\n\
+            RRULE:FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,WE;UNTIL=20240722\n\
+            SUMMARY:Closed\n\
+            END:VEVENT\n";
+
+        // Act
+        let days_off = parse_ics(text).unwrap();
+
+        // Assert
+        let dates: Vec<NaiveDate> = days_off.iter().map(|(date, _)| *date).collect();
+        assert!(dates == [date("2024-07-03"), date("2024-07-15"), date("2024-07-17")]);
+    }
+
+    #[test]
+    fn parse_ics_with_unsupported_recurrence() {
+        // Arrange
+        let text = "BEGIN:VEVENT\n\
+            DTSTART;VALUE=DATE:20240105\n\
+            RRULE:FREQ=MONTHLY;BYDAY=1FR\n\
+            SUMMARY:Inventory day\n\
+            END:VEVENT\n";
+
+        // Act
+        let days_off = parse_ics(text);
+
+        // Assert
+        assert!(
+            days_off.unwrap_err()
+                == "Recurring event `Inventory day` has an unsupported RRULE `FREQ=MONTHLY;BYDAY=1FR`. Only FREQ=YEARLY and FREQ=WEEKLY are supported."
+        );
+    }
+
+    #[test]
+    fn parse_ics_without_start() {
+        // Arrange
+        let text = "BEGIN:VEVENT\nSUMMARY:Broken\nEND:VEVENT\n";
+
+        // Act
+        let days_off = parse_ics(text);
+
+        // Assert
+        assert!(days_off.is_err());
+    }
+
+    #[test]
+    fn parse_csv_with_header() {
+        // Arrange
+        let text = "date,name\n2024-12-30,Year-end\n# Comment\n2024/12/31\n";
+
+        // Act
+        let days_off = parse_csv(text).unwrap();
+
+        // Assert
+        assert!(
+            days_off
+                == vec![
+                    (date("2024-12-30"), "Year-end".to_string()),
+                    (date("2024-12-31"), "Day off".to_string()),
+                ]
+        );
+    }
+
+    #[test]
+    fn parse_csv_with_invalid_date() {
+        // Arrange
+        let text = "2024-12-30\nDecember 31\n";
+
+        // Act
+        let days_off = parse_csv(text);
+
+        // Assert
+        assert!(days_off.unwrap_err() == "Invalid date `December 31` at line 2");
+    }
+
+    #[test]
+    fn company_calendar_takes_precedence() {
+        // Arrange
+        let calendar = Calendar {
+            japanese_holidays: true,
+            days_off: BTreeMap::from([(date("2024-01-01"), "Closed".to_string())]),
+        };
+
+        // Act
+        let new_year = calendar.holiday(date("2024-01-01"));
+        let coming_of_age_day = calendar.holiday(date("2024-01-08"));
+        let working_day = calendar.holiday(date("2024-01-09"));
+
+        // Assert
+        assert!(new_year == Some("Closed"));
+        assert!(coming_of_age_day == Some("成人の日"));
+        assert!(working_day.is_none());
+    }
+}
@@ -1,10 +1,69 @@
-use clap::{Args, Parser, Subcommand};
+use std::{fmt::Display, path::PathBuf, str::FromStr};
+
+use chrono::{Datelike, Local, NaiveDate};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use jobcan::{ApprovalState, ClockTime, LeavePeriod, Stamp};
+
+use crate::{credential_store::CredentialStore, output::OutputFormat};
 
 #[derive(Debug, Parser)]
 #[command(author, version, about, long_about = None)]
 pub struct Cli {
     #[clap(subcommand)]
     pub sub_command: SubCommand,
+
+    #[clap(flatten)]
+    pub endpoints: Endpoints,
+
+    #[clap(
+        short,
+        long,
+        global = true,
+        value_enum,
+        help = "Output format of read commands. Default to $JOBCAN_OUTPUT, the profile, or text if not set.",
+        env = "JOBCAN_OUTPUT"
+    )]
+    pub output: Option<OutputFormat>,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Config file. Default to $JOBCAN_CONFIG, or $XDG_CONFIG_HOME/jobcan-cli/config.toml if not set.",
+        env = "JOBCAN_CONFIG"
+    )]
+    pub config: Option<PathBuf>,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "NAME",
+        help = "Profile in the config file. Default to $JOBCAN_PROFILE, or default_profile of the config file if not set.",
+        env = "JOBCAN_PROFILE"
+    )]
+    pub profile: Option<String>,
+
+    #[clap(
+        long,
+        global = true,
+        value_enum,
+        default_value_t,
+        help = "Where `auth login` saves the password.",
+        env = "JOBCAN_CREDENTIAL_STORE"
+    )]
+    pub credential_store: CredentialStore,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Times to retry connection errors, timeouts and 5xx responses of Jobcan. Default to $JOBCAN_RETRIES, the config file, or 3 if not set.",
+        env = "JOBCAN_RETRIES"
+    )]
+    pub retries: Option<u32>,
+
+    #[clap(flatten)]
+    pub http: Http,
 }
 
 #[derive(Debug, Subcommand)]
@@ -22,6 +81,9 @@ pub enum SubCommand {
 
         #[clap(flatten)]
         note: Notes,
+
+        #[clap(flatten)]
+        guards: Guards,
     },
 
     #[clap(about = "Login to Jobcan and clock out")]
@@ -37,6 +99,9 @@ pub enum SubCommand {
 
         #[clap(flatten)]
         note: Notes,
+
+        #[clap(flatten)]
+        guards: Guards,
     },
 
     #[clap(about = "Login to Jobcan and start break")]
@@ -52,6 +117,9 @@ pub enum SubCommand {
 
         #[clap(flatten)]
         note: Notes,
+
+        #[clap(flatten)]
+        guards: Guards,
     },
 
     #[clap(about = "Login to Jobcan and end break")]
@@ -67,13 +135,151 @@ pub enum SubCommand {
 
         #[clap(flatten)]
         note: Notes,
+
+        #[clap(flatten)]
+        guards: Guards,
     },
 
     #[clap(about = "Login to Jobcan and get current working status")]
-    Status(Credentials),
+    Status {
+        #[clap(flatten)]
+        credentials: Credentials,
+
+        #[clap(
+            short,
+            long,
+            help = "Show today's stamps with elapsed work and break time."
+        )]
+        verbose: bool,
+    },
 
     #[clap(about = "Login to Jobcan and list groups which you belong to")]
     ListGroups(Credentials),
+
+    #[clap(about = "Login to Jobcan and show attendance record of a month")]
+    Attendance {
+        #[clap(flatten)]
+        credentials: Credentials,
+
+        #[clap(flatten)]
+        month: Month,
+    },
+
+    #[clap(about = "Login to Jobcan and request a correction of a stamp (打刻修正申請)")]
+    Correction {
+        #[clap(flatten)]
+        credentials: Credentials,
+
+        #[clap(flatten)]
+        correction: CorrectionStamp,
+
+        #[clap(flatten)]
+        group_id: GroupID,
+    },
+
+    #[clap(about = "Show leave balance or apply for leave (休暇申請)")]
+    Leave {
+        #[clap(subcommand)]
+        sub_command: LeaveCommand,
+    },
+
+    #[clap(about = "Apply for overtime (残業申請) or list the applications")]
+    Overtime {
+        #[clap(subcommand)]
+        sub_command: OvertimeCommand,
+    },
+
+    #[clap(about = "List applications such as corrections, leave and overtime")]
+    Requests {
+        #[clap(subcommand)]
+        sub_command: RequestsCommand,
+    },
+
+    #[clap(about = "Show the planned shifts (シフト) of a month")]
+    Shift {
+        #[clap(subcommand)]
+        sub_command: ShiftCommand,
+    },
+
+    #[clap(
+        about = "Stamp on the schedule of the config file until stopped, e.g. as a systemd service"
+    )]
+    Daemon(Credentials),
+
+    #[clap(about = "Save or delete the password used when --password is not given")]
+    Auth {
+        #[clap(subcommand)]
+        sub_command: AuthCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AuthCommand {
+    #[clap(about = "Login to Jobcan and save the password to the credential store")]
+    Login(Credentials),
+
+    #[clap(about = "Delete the saved password and login session")]
+    Logout(Email),
+}
+
+#[derive(Debug, Subcommand)]
+pub enum LeaveCommand {
+    #[clap(about = "Login to Jobcan and show remaining days of each leave type")]
+    Balance(Credentials),
+
+    #[clap(about = "Login to Jobcan and apply for leave")]
+    Request {
+        #[clap(flatten)]
+        credentials: Credentials,
+
+        #[clap(flatten)]
+        leave: LeaveForm,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum OvertimeCommand {
+    #[clap(about = "Login to Jobcan and apply for overtime")]
+    Request {
+        #[clap(flatten)]
+        credentials: Credentials,
+
+        #[clap(flatten)]
+        overtime: OvertimeForm,
+    },
+
+    #[clap(about = "Login to Jobcan and list overtime applications of a month")]
+    List {
+        #[clap(flatten)]
+        credentials: Credentials,
+
+        #[clap(flatten)]
+        month: Month,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum RequestsCommand {
+    #[clap(about = "Login to Jobcan and list submitted applications with their states")]
+    List {
+        #[clap(flatten)]
+        credentials: Credentials,
+
+        #[clap(flatten)]
+        filter: RequestFilter,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ShiftCommand {
+    #[clap(about = "Login to Jobcan and show the planned start, end and group of each day")]
+    Show {
+        #[clap(flatten)]
+        credentials: Credentials,
+
+        #[clap(flatten)]
+        month: Month,
+    },
 }
 
 #[derive(Debug, Args)]
@@ -81,7 +287,7 @@ pub struct Credentials {
     #[clap(
         short,
         long,
-        help = "Account email. Default to $JOBCAN_EMAIL if not set.",
+        help = "Account email. Default to $JOBCAN_EMAIL, or the profile if not set.",
         env = "JOBCAN_EMAIL"
     )]
     pub email: Option<String>,
@@ -89,42 +295,392 @@ pub struct Credentials {
     #[clap(
         short,
         long,
-        help = "Account password. Default to $JOBCAN_PASSWORD if not set.",
-        env = "JOBCAN_PASSWORD"
+        help = "Account password. Default to $JOBCAN_PASSWORD, the password saved by `auth login`, or a prompt if not set.",
+        env = "JOBCAN_PASSWORD",
+        hide_env_values = true
     )]
     pub password: Option<String>,
+
+    #[clap(flatten)]
+    pub password_source: PasswordSource,
+}
+
+/// Ways to pass the password without putting it in arguments or environment variables.
+/// They take precedence over `--password` and `$JOBCAN_PASSWORD`.
+// Note: Not conflicting with `--password`, since clap would also reject `$JOBCAN_PASSWORD`
+#[derive(Debug, Args)]
+#[group(multiple = false)]
+pub struct PasswordSource {
+    #[clap(long, help = "Read the password from the first line of stdin.")]
+    pub password_stdin: bool,
+
+    #[clap(
+        long,
+        value_name = "PATH",
+        help = "Read the password from the first line of a file."
+    )]
+    pub password_file: Option<PathBuf>,
+
+    #[clap(
+        long,
+        value_name = "COMMAND",
+        help = "Read the password from the first line of the output of a shell command, e.g. `pass show jobcan`."
+    )]
+    pub password_command: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct Email {
+    #[clap(
+        short,
+        long,
+        help = "Account email. Default to $JOBCAN_EMAIL, or the profile if not set.",
+        env = "JOBCAN_EMAIL"
+    )]
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct Endpoints {
+    #[clap(
+        long,
+        global = true,
+        hide = true,
+        help = "Base URL of the Jobcan sign-in service. Default to $JOBCAN_ID_URL if not set.",
+        env = "JOBCAN_ID_URL"
+    )]
+    pub id_url: Option<String>,
+
+    #[clap(
+        long,
+        global = true,
+        hide = true,
+        help = "Base URL of the Jobcan attendance service. Default to $JOBCAN_SSL_URL if not set.",
+        env = "JOBCAN_SSL_URL"
+    )]
+    pub ssl_url: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct Http {
+    #[clap(
+        long,
+        global = true,
+        value_name = "SECS",
+        help = "Timeout of connecting to Jobcan. Default to $JOBCAN_CONNECT_TIMEOUT, or the config file if not set.",
+        env = "JOBCAN_CONNECT_TIMEOUT"
+    )]
+    pub connect_timeout: Option<u64>,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "SECS",
+        help = "Timeout of each request to Jobcan. Default to $JOBCAN_TIMEOUT, or the config file if not set.",
+        env = "JOBCAN_TIMEOUT"
+    )]
+    pub timeout: Option<u64>,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "URL",
+        help = "HTTP(S) proxy, e.g. http://proxy.example.com:8080. Default to $JOBCAN_PROXY, the config file, or $HTTPS_PROXY if not set.",
+        env = "JOBCAN_PROXY",
+        // Note: The URL may contain credentials of the proxy
+        hide_env_values = true
+    )]
+    pub proxy: Option<String>,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "USER",
+        help = "User of the proxy, whose password is read from $JOBCAN_PROXY_PASSWORD. Default to $JOBCAN_PROXY_USER, or the config file if not set.",
+        env = "JOBCAN_PROXY_USER"
+    )]
+    pub proxy_user: Option<String>,
+
+    #[clap(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "PEM file of a root certificate to trust in addition to the system ones. Can be repeated. Default to the config file if not set."
+    )]
+    pub ca_cert: Vec<PathBuf>,
+
+    #[clap(
+        long,
+        global = true,
+        help = "User-Agent header of requests. Default to $JOBCAN_USER_AGENT, the config file, or jobcan-cli/<VERSION> if not set.",
+        env = "JOBCAN_USER_AGENT"
+    )]
+    pub user_agent: Option<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct GroupID {
     #[clap(
         long,
-        help = "Group ID. Default to $JOBCAN_GROUP_ID if not set.",
-        env = "JOBCAN_GROUP_ID"
+        help = "Group ID. Default to $JOBCAN_GROUP_ID, the profile, or the default group if not set."
     )]
     pub group_id: Option<String>,
+
+    #[clap(
+        long,
+        value_name = "NAME",
+        help = "Group name, matched exactly, case-insensitively or by a unique prefix. Takes precedence over --group-id. Default to $JOBCAN_GROUP, or the profile if not set."
+    )]
+    pub group: Option<String>,
 }
 
 #[derive(Debug, Args)]
 pub struct NightShift {
-    #[clap(long, default_value = "false", help = "Night-Shift mode.")]
+    #[clap(
+        long,
+        overrides_with = "no_night_shift",
+        help = "Night-Shift mode. Default to the profile, or off if not set."
+    )]
     pub night_shift: bool,
+
+    #[clap(
+        long,
+        overrides_with = "night_shift",
+        help = "Disable Night-Shift mode."
+    )]
+    pub no_night_shift: bool,
 }
 
-impl Into<bool> for NightShift {
-    fn into(self) -> bool {
-        self.night_shift
+impl From<NightShift> for Option<bool> {
+    fn from(value: NightShift) -> Self {
+        match value {
+            NightShift {
+                night_shift: true, ..
+            } => Some(true),
+            NightShift {
+                no_night_shift: true,
+                ..
+            } => Some(false),
+            _ => None,
+        }
     }
 }
 
+/// Conditions under which a stamp is skipped instead of made.
+#[derive(Debug, Args)]
+pub struct Guards {
+    #[clap(
+        long,
+        help = "Do nothing instead of failing when the stamp is not allowed in the current working status, e.g. clock-in while working."
+    )]
+    pub if_needed: bool,
+
+    #[clap(
+        long,
+        help = "Do nothing on a Japanese public holiday or a day off of the company calendars in the config file."
+    )]
+    pub skip_holidays: bool,
+}
+
 #[derive(Debug, Args)]
 pub struct Notes {
-    #[clap(long, default_value = "", help = "Notes to be added to the stamp.")]
-    pub notes: String,
+    #[clap(
+        long,
+        help = "Notes to be added to the stamp. Default to the profile, or empty if not set."
+    )]
+    pub notes: Option<String>,
+}
+
+impl From<Notes> for Option<String> {
+    fn from(value: Notes) -> Self {
+        value.notes
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct Month {
+    #[clap(
+        long,
+        value_name = "YYYY-MM",
+        help = "Month to show. Default to the current month."
+    )]
+    pub month: Option<YearMonth>,
+}
+
+impl From<Month> for YearMonth {
+    fn from(value: Month) -> Self {
+        value.month.unwrap_or_else(|| {
+            let today = Local::now().date_naive();
+            YearMonth {
+                year: today.year(),
+                month: today.month(),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct CorrectionStamp {
+    #[clap(
+        long,
+        value_name = "YYYY-MM-DD",
+        help = "Date of the stamp. Default to today."
+    )]
+    pub date: Option<NaiveDate>,
+
+    #[clap(
+        long,
+        value_name = "HH:MM",
+        help = "Time of the stamp, e.g. 26:00 for 2:00 of the next day on a night shift."
+    )]
+    pub time: ClockTime,
+
+    #[clap(long, value_enum, help = "Kind of the stamp.")]
+    pub stamp: StampType,
+
+    #[clap(long, help = "Reason of the correction, which Jobcan requires.")]
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum StampType {
+    ClockIn,
+    ClockOut,
+    StartBreak,
+    EndBreak,
+}
+
+impl From<StampType> for Stamp {
+    fn from(value: StampType) -> Self {
+        match value {
+            StampType::ClockIn => Stamp::ClockIn,
+            StampType::ClockOut => Stamp::ClockOut,
+            StampType::StartBreak => Stamp::StartBreak,
+            StampType::EndBreak => Stamp::EndBreak,
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct LeaveForm {
+    #[clap(long, value_name = "YYYY-MM-DD", help = "Date of the leave.")]
+    pub date: NaiveDate,
+
+    #[clap(
+        long = "type",
+        value_name = "NAME",
+        help = "Leave type, e.g. 有給休暇, matched exactly, case-insensitively or by a unique prefix."
+    )]
+    pub leave_type: String,
+
+    #[clap(long, value_enum, help = "Take a half day off instead of a full day.")]
+    pub half_day: Option<HalfDay>,
+
+    #[clap(long, help = "Reason of the leave. Default to empty.")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct OvertimeForm {
+    #[clap(
+        long,
+        value_name = "YYYY-MM-DD",
+        help = "Date of the overtime. Default to today."
+    )]
+    pub date: Option<NaiveDate>,
+
+    #[clap(
+        long,
+        value_name = "HH:MM",
+        help = "Time the overtime is planned to end, e.g. 25:00 for 1:00 of the next day."
+    )]
+    pub end_time: ClockTime,
+
+    #[clap(long, help = "Reason of the overtime, which Jobcan requires.")]
+    pub reason: String,
+}
+
+#[derive(Debug, Args)]
+pub struct RequestFilter {
+    #[clap(
+        long,
+        value_enum,
+        help = "Only list applications in this state. Can be repeated."
+    )]
+    pub state: Vec<RequestState>,
+
+    #[clap(
+        long,
+        value_name = "YYYY-MM-DD",
+        help = "Only list applications for this date or later."
+    )]
+    pub from: Option<NaiveDate>,
+
+    #[clap(
+        long,
+        value_name = "YYYY-MM-DD",
+        help = "Only list applications for this date or earlier."
+    )]
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum RequestState {
+    Applied,
+    Pending,
+    Approved,
+    Rejected,
+    Canceled,
+}
+
+impl From<RequestState> for ApprovalState {
+    fn from(value: RequestState) -> Self {
+        match value {
+            RequestState::Applied => ApprovalState::Applied,
+            RequestState::Pending => ApprovalState::Pending,
+            RequestState::Approved => ApprovalState::Approved,
+            RequestState::Rejected => ApprovalState::Rejected,
+            RequestState::Canceled => ApprovalState::Canceled,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HalfDay {
+    Morning,
+    Afternoon,
+}
+
+impl From<HalfDay> for LeavePeriod {
+    fn from(value: HalfDay) -> Self {
+        match value {
+            HalfDay::Morning => LeavePeriod::Morning,
+            HalfDay::Afternoon => LeavePeriod::Afternoon,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YearMonth {
+    pub year: i32,
+    pub month: u32,
+}
+
+impl FromStr for YearMonth {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NaiveDate::parse_from_str(&format!("{}-01", s), "%Y-%m-%d")
+            .map(|date| YearMonth {
+                year: date.year(),
+                month: date.month(),
+            })
+            .map_err(|_| format!("`{}` is not in YYYY-MM format", s))
+    }
 }
 
-impl Into<String> for Notes {
-    fn into(self) -> String {
-        self.notes
+impl Display for YearMonth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}-{:02}", self.year, self.month)
     }
 }
@@ -0,0 +1,100 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::{NaiveTime, Timelike};
+
+/// Time of a working day in `HH:MM`. Past midnight, the hour exceeds 23 as Jobcan shows it,
+/// e.g. `25:30` for 1:30 of the next day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClockTime {
+    minutes: u32,
+}
+
+impl ClockTime {
+    /// Jobcan takes times until the end of the next day.
+    const MAX_HOUR: u32 = 47;
+
+    /// Fails if `hour` exceeds 47 or `minute` exceeds 59.
+    pub fn new(hour: u32, minute: u32) -> Option<ClockTime> {
+        (hour <= Self::MAX_HOUR && minute < 60).then_some(ClockTime {
+            minutes: hour * 60 + minute,
+        })
+    }
+
+    pub fn hour(&self) -> u32 {
+        self.minutes / 60
+    }
+
+    pub fn minute(&self) -> u32 {
+        self.minutes % 60
+    }
+
+    /// Time in `HHMM`, as the forms of Jobcan take it.
+    pub fn to_request_params(&self) -> String {
+        format!("{:02}{:02}", self.hour(), self.minute())
+    }
+}
+
+impl From<NaiveTime> for ClockTime {
+    fn from(time: NaiveTime) -> Self {
+        ClockTime {
+            minutes: time.hour() * 60 + time.minute(),
+        }
+    }
+}
+
+impl Display for ClockTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.pad(&format!("{:02}:{:02}", self.hour(), self.minute()))
+    }
+}
+
+impl FromStr for ClockTime {
+    type Err = String;
+
+    /// Parse `H:MM` or `HH:MM`, e.g. `9:00` or `25:30`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid time `{}`. Use HH:MM, e.g. 18:30 or 25:00", s);
+        let (hour, minute) = s.split_once(':').ok_or_else(invalid)?;
+        let is_digits = |text: &str, len: std::ops::RangeInclusive<usize>| {
+            len.contains(&text.len()) && text.bytes().all(|b| b.is_ascii_digit())
+        };
+        if !is_digits(hour, 1..=2) || !is_digits(minute, 2..=2) {
+            return Err(invalid());
+        }
+        ClockTime::new(hour.parse().unwrap(), minute.parse().unwrap()).ok_or_else(invalid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_time_past_midnight() {
+        // Arrange
+        let text = "25:30";
+
+        // Act
+        let time: ClockTime = text.parse().unwrap();
+
+        // Assert
+        assert!(time.hour() == 25 && time.minute() == 30);
+        assert!(time.to_string() == "25:30");
+        assert!(time.to_request_params() == "2530");
+        assert!(time > ClockTime::from(NaiveTime::from_hms_opt(23, 59, 0).unwrap()));
+    }
+
+    #[test]
+    fn parse_invalid_times() {
+        // Arrange
+        let texts = ["48:00", "9:60", "9:5", "0930", "-1:00", ""];
+
+        // Act
+        let results: Vec<Result<ClockTime, String>> =
+            texts.iter().map(|text| text.parse()).collect();
+
+        // Assert
+        assert!(results.iter().all(Result::is_err));
+        assert!("9:05".parse::<ClockTime>().unwrap().to_string() == "09:05");
+    }
+}
@@ -0,0 +1,177 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::Deserialize;
+
+use crate::{calendar::CalendarConfig, output::OutputFormat, schedule::Schedule};
+
+/// Settings read from `$XDG_CONFIG_HOME/jobcan-cli/config.toml`, or the file given by `--config`.
+///
+/// ```toml
+/// default_profile = "work-a"
+///
+/// [endpoints]
+/// id_url = "http://localhost:8080"
+/// ssl_url = "http://localhost:8080"
+///
+/// [http]
+/// connect_timeout = 10
+/// timeout = 30
+/// proxy = "http://proxy.example.com:8080"
+/// proxy_user = "alice"
+/// ca_certs = ["/etc/ssl/certs/corporate-ca.pem"]
+/// user_agent = "jobcan-cli"
+///
+/// [retry]
+/// max_retries = 3
+/// initial_backoff_ms = 500
+/// max_backoff_ms = 8000
+///
+/// [calendar]
+/// japanese_holidays = true
+/// files = ["/path/to/company.ics", "/path/to/days-off.csv"]
+///
+/// [schedule]
+/// skip_dates = ["2024-12-31"]
+/// skip_holidays = true
+///
+/// [schedule.monday]
+/// clock_in = "09:00"
+/// clock_out = "18:00"
+/// breaks = [{ start = "12:00", end = "13:00" }]
+///
+/// [profiles.work-a]
+/// email = "user@example.com"
+/// group = "Head Office"
+/// notes = "remote"
+/// night_shift = false
+/// output = "json"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Config {
+    /// Profile used when neither `--profile` nor `$JOBCAN_PROFILE` is given.
+    pub default_profile: Option<String>,
+
+    #[serde(default)]
+    pub endpoints: Endpoints,
+
+    #[serde(default)]
+    pub http: Http,
+
+    #[serde(default)]
+    pub retry: Retry,
+
+    #[serde(default)]
+    pub schedule: Schedule,
+
+    #[serde(default)]
+    pub calendar: CalendarConfig,
+
+    #[serde(default)]
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Endpoints {
+    pub id_url: Option<String>,
+    pub ssl_url: Option<String>,
+}
+
+/// Options of the HTTP client. Timeouts are in seconds.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Http {
+    pub connect_timeout: Option<u64>,
+    pub timeout: Option<u64>,
+    pub proxy: Option<String>,
+    pub proxy_user: Option<String>,
+    #[serde(default)]
+    pub ca_certs: Vec<PathBuf>,
+    pub user_agent: Option<String>,
+}
+
+/// Retry of transient failures, see [`jobcan::RetryPolicy`].
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Retry {
+    pub max_retries: Option<u32>,
+    pub initial_backoff_ms: Option<u64>,
+    pub max_backoff_ms: Option<u64>,
+}
+
+/// Defaults for the options of a command, overridden by flags and environment variables.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Profile {
+    pub email: Option<String>,
+    pub group_id: Option<String>,
+    /// Group name, taking precedence over `group_id`.
+    pub group: Option<String>,
+    pub notes: Option<String>,
+    pub night_shift: Option<bool>,
+    pub output: Option<OutputFormat>,
+}
+
+impl Config {
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("jobcan-cli").join("config.toml"))
+    }
+
+    /// Load `path`, or the default config file if `path` is not given, with the profile named
+    /// `profile` (see [`Config::profile`]). Unlike a file given explicitly, a missing default
+    /// file yields the default config.
+    pub fn load(path: Option<&Path>, profile: Option<&str>) -> Result<(Config, Profile), String> {
+        let (path, required) = match path {
+            Some(path) => (PathBuf::from(path), true),
+            None => match Self::default_path() {
+                Some(path) => (path, false),
+                None => return Self::with_profile(Config::default(), profile),
+            },
+        };
+
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound && !required => {
+                return Self::with_profile(Config::default(), profile)
+            }
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+
+        let config: Config = toml::from_str(&text)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        let (config, profile) = Self::with_profile(config, profile)?;
+        // Note: The schedule is checked against the profile, whose night shift it falls back to
+        config
+            .schedule
+            .validate(profile.night_shift)
+            .map_err(|e| format!("Invalid {}: {}", path.display(), e))?;
+        Ok((config, profile))
+    }
+
+    fn with_profile(config: Config, name: Option<&str>) -> Result<(Config, Profile), String> {
+        let profile = config.profile(name)?;
+        Ok((config, profile))
+    }
+
+    /// Profile named `name`, or `default_profile` if `name` is not given. Without either,
+    /// an empty profile is returned.
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile, String> {
+        let Some(name) = name.or(self.default_profile.as_deref()) else {
+            return Ok(Profile::default());
+        };
+
+        self.profiles.get(name).cloned().ok_or_else(|| {
+            let names: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            format!(
+                "Profile `{}` is not found in the config file. Available profiles: [{}]",
+                name,
+                names.join(", ")
+            )
+        })
+    }
+}
@@ -0,0 +1,56 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{approval_state::ApprovalState, clock_time::ClockTime, stamp::Stamp};
+
+/// A stamp correction request (打刻修正申請) to submit with
+/// [`crate::Jobcan::request_correction`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrectionRequest {
+    pub date: NaiveDate,
+    /// Time of the stamp, past 23:59 for a stamp after midnight of a night shift started on
+    /// `date`.
+    pub time: ClockTime,
+    pub stamp: Stamp,
+    pub group_id: String,
+    /// Reason of the request (申請理由), which Jobcan requires.
+    pub reason: String,
+}
+
+/// A stamp listed on the correction page of a day, with the state of its request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Correction {
+    pub(crate) date: NaiveDate,
+    pub(crate) time: String,
+    pub(crate) stamp: Stamp,
+    pub(crate) group: Option<String>,
+    pub(crate) reason: Option<String>,
+    pub(crate) state: ApprovalState,
+}
+
+impl Correction {
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Time of the stamp in `HH:MM`. The hour exceeds 23 for a night shift.
+    pub fn time(&self) -> &str {
+        &self.time
+    }
+
+    pub fn stamp(&self) -> Stamp {
+        self.stamp
+    }
+
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub fn state(&self) -> ApprovalState {
+        self.state
+    }
+}
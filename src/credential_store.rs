@@ -0,0 +1,297 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use argon2::Argon2;
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use chacha20poly1305::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+use clap::ValueEnum;
+use jobcan::{ExposeSecret, SecretString};
+use secrecy::zeroize::Zeroizing;
+use serde::{Deserialize, Serialize};
+
+/// Service name of keyring entries, whose user name is the account email.
+const SERVICE: &str = "jobcan-cli";
+
+/// Environment variable holding the passphrase of the credential file.
+pub const PASSPHRASE_ENV: &str = "JOBCAN_CREDENTIAL_PASSPHRASE";
+
+/// Where `auth login` saves passwords.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum CredentialStore {
+    /// The OS keyring, or the credential file if no keyring is available
+    #[default]
+    Auto,
+    /// The OS keyring (Secret Service, macOS Keychain or Windows Credential Manager)
+    Keyring,
+    /// A file encrypted with $JOBCAN_CREDENTIAL_PASSPHRASE, for headless machines
+    File,
+}
+
+impl CredentialStore {
+    pub fn get(self, email: &str) -> Result<Option<SecretString>, String> {
+        self.with_keyring(
+            email,
+            |entry| match entry.get_password() {
+                Ok(password) => Ok(Some(SecretString::new(password))),
+                Err(keyring::Error::NoEntry) => Ok(None),
+                Err(e) => Err(e),
+            },
+            || CredentialFile::open()?.get(email),
+        )
+    }
+
+    pub fn set(self, email: &str, password: &SecretString) -> Result<(), String> {
+        self.with_keyring(
+            email,
+            |entry| entry.set_password(password.expose_secret()),
+            || CredentialFile::open()?.set(email, password),
+        )
+    }
+
+    /// Returns whether a password was saved.
+    pub fn delete(self, email: &str) -> Result<bool, String> {
+        self.with_keyring(
+            email,
+            |entry| match entry.delete_password() {
+                Ok(()) => Ok(true),
+                Err(keyring::Error::NoEntry) => Ok(false),
+                Err(e) => Err(e),
+            },
+            || CredentialFile::open()?.delete(email),
+        )
+    }
+
+    /// Run `op` on the keyring entry of `email`, or `fallback` on the credential file.
+    fn with_keyring<T>(
+        self,
+        email: &str,
+        op: impl FnOnce(&keyring::Entry) -> keyring::Result<T>,
+        fallback: impl FnOnce() -> Result<T, String>,
+    ) -> Result<T, String> {
+        if self == CredentialStore::File {
+            return fallback();
+        }
+
+        match keyring::Entry::new(SERVICE, email).and_then(|entry| op(&entry)) {
+            Ok(value) => Ok(value),
+            // Note: e.g. no Secret Service is running on a headless Linux
+            Err(keyring::Error::PlatformFailure(_) | keyring::Error::NoStorageAccess(_))
+                if self == CredentialStore::Auto =>
+            {
+                fallback()
+            }
+            Err(e) => Err(format!("Failed to access the keyring: {}", e)),
+        }
+    }
+}
+
+/// Passwords encrypted with XChaCha20-Poly1305, keyed by account email.
+struct CredentialFile {
+    path: PathBuf,
+    entries: BTreeMap<String, EncryptedPassword>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct EncryptedPassword {
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl CredentialFile {
+    fn path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("jobcan-cli").join("credentials.json"))
+    }
+
+    fn open() -> Result<CredentialFile, String> {
+        let path = Self::path().ok_or("Failed to find the data directory")?;
+        let entries = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+        };
+        Ok(CredentialFile { path, entries })
+    }
+
+    fn get(&self, email: &str) -> Result<Option<SecretString>, String> {
+        // Note: The passphrase is only required if the file has the password
+        self.entries
+            .get(email)
+            .map(|encrypted| encrypted.decrypt(&passphrase()?))
+            .transpose()
+    }
+
+    fn set(mut self, email: &str, password: &SecretString) -> Result<(), String> {
+        let encrypted = EncryptedPassword::encrypt(password, &passphrase()?)?;
+        self.entries.insert(email.into(), encrypted);
+        self.save()
+    }
+
+    fn delete(mut self, email: &str) -> Result<bool, String> {
+        if self.entries.remove(email).is_none() {
+            return Ok(false);
+        }
+        self.save()?;
+        Ok(true)
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let error = |e: io::Error| format!("Failed to write {}: {}", self.path.display(), e);
+        let json = serde_json::to_vec_pretty(&self.entries).expect("Failed to write JSON");
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir).map_err(error)?;
+        }
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        // Note: The file is replaced by a rename, so that an interruption never loses the
+        // passwords of other accounts. The temporary file is in the same directory, so that the
+        // rename doesn't cross file systems
+        let mut temp_path = self.path.as_os_str().to_owned();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+        let temp_path = PathBuf::from(temp_path);
+        options
+            .open(&temp_path)
+            .and_then(|mut file| file.write_all(&json))
+            .and_then(|()| fs::rename(&temp_path, &self.path))
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                error(e)
+            })
+    }
+}
+
+fn passphrase() -> Result<Zeroizing<String>, String> {
+    env::var(PASSPHRASE_ENV)
+        .ok()
+        .filter(|passphrase| !passphrase.is_empty())
+        .map(Zeroizing::new)
+        .ok_or_else(|| {
+            format!(
+                "${} is required to use the credential file.",
+                PASSPHRASE_ENV
+            )
+        })
+}
+
+impl EncryptedPassword {
+    fn encrypt(password: &SecretString, passphrase: &str) -> Result<EncryptedPassword, String> {
+        let mut salt = [0u8; 16];
+        OsRng.fill_bytes(&mut salt);
+        let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = Self::cipher(passphrase, &salt)?
+            .encrypt(&nonce, password.expose_secret().as_bytes())
+            .map_err(|_| "Failed to encrypt the password")?;
+
+        Ok(EncryptedPassword {
+            salt: BASE64.encode(salt),
+            nonce: BASE64.encode(nonce),
+            ciphertext: BASE64.encode(ciphertext),
+        })
+    }
+
+    fn decrypt(&self, passphrase: &str) -> Result<SecretString, String> {
+        let invalid = |_| "The credential file is broken".to_string();
+        let salt = BASE64.decode(&self.salt).map_err(invalid)?;
+        let nonce = BASE64.decode(&self.nonce).map_err(invalid)?;
+        let ciphertext = BASE64.decode(&self.ciphertext).map_err(invalid)?;
+        if nonce.len() != 24 {
+            return Err("The credential file is broken".into());
+        }
+
+        let plaintext = Self::cipher(passphrase, &salt)?
+            .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| format!("Failed to decrypt the password. Check ${}.", PASSPHRASE_ENV))?;
+        let plaintext = Zeroizing::new(plaintext);
+        std::str::from_utf8(&plaintext)
+            .map(|password| SecretString::new(password.into()))
+            .map_err(|_| "The credential file is broken".into())
+    }
+
+    fn cipher(passphrase: &str, salt: &[u8]) -> Result<XChaCha20Poly1305, String> {
+        let mut key = Zeroizing::new([0u8; 32]);
+        Argon2::default()
+            .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+            .map_err(|e| format!("Failed to derive the key: {}", e))?;
+        Ok(XChaCha20Poly1305::new(key.as_ref().into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encrypted_password_is_decrypted_with_same_passphrase() {
+        // Arrange
+        let encrypted =
+            EncryptedPassword::encrypt(&SecretString::new("password".into()), "passphrase")
+                .unwrap();
+
+        // Act
+        let decrypted = encrypted.decrypt("passphrase").unwrap();
+
+        // Assert
+        assert!(decrypted.expose_secret() == "password");
+        assert!(encrypted.ciphertext != BASE64.encode("password"));
+    }
+
+    #[test]
+    fn encrypted_password_is_not_decrypted_with_other_passphrase() {
+        // Arrange
+        let encrypted =
+            EncryptedPassword::encrypt(&SecretString::new("password".into()), "passphrase")
+                .unwrap();
+
+        // Act
+        let decrypted = encrypted.decrypt("other");
+
+        // Assert
+        assert!(decrypted.is_err());
+    }
+
+    #[test]
+    fn save_replaces_file_without_leaving_temporary_file() {
+        // Arrange
+        let dir = std::env::temp_dir().join(format!("jobcan-credentials-{}", std::process::id()));
+        let path = dir.join("credentials.json");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "{}").unwrap();
+        let encrypted =
+            EncryptedPassword::encrypt(&SecretString::new("password".into()), "passphrase")
+                .unwrap();
+        let file = CredentialFile {
+            path: path.clone(),
+            entries: BTreeMap::from([("user@example.com".to_string(), encrypted.clone())]),
+        };
+
+        // Act
+        file.save().unwrap();
+        let saved: BTreeMap<String, EncryptedPassword> =
+            serde_json::from_slice(&fs::read(&path).unwrap()).unwrap();
+        let names: Vec<_> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        fs::remove_dir_all(&dir).unwrap();
+
+        // Assert
+        assert!(saved.get("user@example.com") == Some(&encrypted));
+        assert!(names == ["credentials.json"]);
+    }
+}
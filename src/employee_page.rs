@@ -0,0 +1,41 @@
+use crate::{html_extractor::Group, stamp_log::StampLog, working_status::WorkingStatus};
+
+/// A snapshot of the employee page, parsed in one pass by
+/// [`crate::html_extractor::HtmlExtractor::employee_page`].
+///
+/// Fetch it once with [`crate::Jobcan::employee_page`] and share it between operations
+/// instead of loading the page for each of them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EmployeePage {
+    pub(crate) token: String,
+    pub(crate) working_status: WorkingStatus,
+    pub(crate) groups: Vec<Group>,
+    pub(crate) default_group_id: String,
+    pub(crate) stamp_logs: Vec<StampLog>,
+}
+
+impl EmployeePage {
+    /// Token to be sent with a stamp.
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn working_status(&self) -> WorkingStatus {
+        self.working_status
+    }
+
+    /// Groups the account can stamp against.
+    pub fn groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    /// Group used by Jobcan when no group is chosen.
+    pub fn default_group_id(&self) -> &str {
+        &self.default_group_id
+    }
+
+    /// Stamps made today, oldest first.
+    pub fn stamp_logs(&self) -> &[StampLog] {
+        &self.stamp_logs
+    }
+}
@@ -0,0 +1,71 @@
+use jobcan::JobcanError;
+use serde::Serialize;
+
+/// Exit status of the CLI, distinct per kind of failure so that wrapper scripts can react
+/// to each. `2` is used by clap for usage errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitCode {
+    /// Email or password is not given.
+    MissingCredentials = 3,
+    /// Jobcan rejected the email or password.
+    AuthError = 4,
+    /// Jobcan could not be reached, e.g. a network outage.
+    RequestError = 5,
+    /// Jobcan answered something unexpected, e.g. a stamp did not change the status.
+    UnexpectedResponse = 6,
+    /// A page of Jobcan could not be parsed, likely because its HTML has changed.
+    ElementExtractError = 7,
+    /// The saved login session could not be read or written.
+    SessionError = 8,
+    /// The config file or an option is invalid.
+    ConfigError = 9,
+    /// The stamp is not allowed in the current working status, e.g. clock-in while working.
+    StampTransitionError = 10,
+    /// The password could not be read from or written to the keyring or the credential file.
+    CredentialStoreError = 11,
+}
+
+/// An error reported to the user before exiting.
+#[derive(Debug, Serialize)]
+pub struct Failure {
+    kind: ExitCode,
+    exit_code: i32,
+    message: String,
+}
+
+impl Failure {
+    pub fn new(kind: ExitCode, message: impl Into<String>) -> Failure {
+        Failure {
+            kind,
+            exit_code: kind as i32,
+            message: message.into(),
+        }
+    }
+
+    pub fn exit_code(&self) -> i32 {
+        self.exit_code
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl From<JobcanError> for Failure {
+    fn from(error: JobcanError) -> Self {
+        let kind = match error {
+            JobcanError::AuthError => ExitCode::AuthError,
+            JobcanError::ReqwestError { .. } => ExitCode::RequestError,
+            JobcanError::UnexpectedResponseError { .. } => ExitCode::UnexpectedResponse,
+            JobcanError::ElementExtractError { .. } => ExitCode::ElementExtractError,
+            JobcanError::SessionError { .. } => ExitCode::SessionError,
+            JobcanError::UrlParseError { .. }
+            | JobcanError::ClientBuildError { .. }
+            | JobcanError::GroupError { .. }
+            | JobcanError::LeaveTypeError { .. } => ExitCode::ConfigError,
+            JobcanError::StampTransitionError { .. } => ExitCode::StampTransitionError,
+        };
+        Failure::new(kind, error.to_string())
+    }
+}
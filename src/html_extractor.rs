@@ -1,9 +1,26 @@
+use chrono::{NaiveDate, NaiveDateTime};
 use regex::Regex;
-use scraper::Html;
+use scraper::{ElementRef, Html};
+use serde::Serialize;
 
-use crate::{error::JobcanError, working_status::WorkingStatus, Result};
+use crate::{
+    application::{Application, ApplicationKind},
+    approval_state::ApprovalState,
+    attendance::AttendanceRecord,
+    correction::Correction,
+    employee_page::EmployeePage,
+    error::JobcanError,
+    leave::{LeaveApplication, LeaveBalance, LeavePeriod, LeaveType},
+    overtime::OvertimeApplication,
+    shift::Shift,
+    stamp::Stamp,
+    stamp_log::StampLog,
+    working_status::WorkingStatus,
+    Result,
+};
 
-#[derive(Debug, PartialEq, Eq)]
+/// A group the employee can stamp against.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct Group {
     id: String,
     name: String,
@@ -17,11 +34,80 @@ impl Group {
     pub fn name(&self) -> &str {
         &self.name
     }
+
+    /// Find the group whose ID is `id` in `groups`.
+    ///
+    /// Fails with [`JobcanError::GroupError`] if the account doesn't belong to the group.
+    pub fn find_by_id<'a>(groups: &'a [Group], id: &str) -> Result<&'a Group> {
+        groups
+            .iter()
+            .find(|group| group.id == id)
+            .ok_or_else(|| JobcanError::GroupError {
+                message: format!("Group ID `{}` is not one of your groups", id),
+                groups: groups.to_vec(),
+            })
+    }
+
+    /// Find the group named `name` in `groups`, trying an exact match, a case-insensitive
+    /// match and a unique case-insensitive prefix in order.
+    ///
+    /// Fails with [`JobcanError::GroupError`] if no group or more than one group matches.
+    pub fn find_by_name<'a>(groups: &'a [Group], name: &str) -> Result<&'a Group> {
+        match_name(groups, name, Group::name).map_err(|candidates| match candidates {
+            Some(candidates) => JobcanError::GroupError {
+                message: format!("Group `{}` is ambiguous", name),
+                groups: candidates.into_iter().cloned().collect(),
+            },
+            None => JobcanError::GroupError {
+                message: format!("Group `{}` is not found", name),
+                groups: groups.to_vec(),
+            },
+        })
+    }
+}
+
+/// Find the item named `name` in `items`, trying an exact match, a case-insensitive match and
+/// a unique case-insensitive prefix in order. Fails with the candidates if more than one item
+/// matches, or with `None` if no item matches.
+pub(crate) fn match_name<'a, T>(
+    items: &'a [T],
+    name: &str,
+    name_of: impl Fn(&T) -> &str,
+) -> std::result::Result<&'a T, Option<Vec<&'a T>>> {
+    let lowercase = name.to_lowercase();
+    let matchers: [&dyn Fn(&str) -> bool; 3] = [
+        &|item| item == name,
+        &|item| item.to_lowercase() == lowercase,
+        &|item| item.to_lowercase().starts_with(&lowercase),
+    ];
+
+    for matcher in matchers {
+        let candidates: Vec<&T> = items.iter().filter(|item| matcher(name_of(item))).collect();
+        match candidates.as_slice() {
+            [] => continue,
+            [item] => return Ok(item),
+            _ => return Err(Some(candidates)),
+        }
+    }
+    Err(None)
 }
 
+/// Scrapers for the pages of Jobcan.
 pub struct HtmlExtractor {}
 
 impl HtmlExtractor {
+    /// Parse everything the client reads from the employee page at once.
+    pub fn employee_page(text: &str) -> Result<EmployeePage> {
+        let html = Html::parse_document(text);
+        Ok(EmployeePage {
+            token: Self::token(&html)?,
+            working_status: Self::working_status(text)?,
+            groups: Self::groups(&html)?,
+            default_group_id: Self::default_group_id(text)?,
+            stamp_logs: Self::stamp_logs(&html),
+        })
+    }
+
     pub fn authenticity_token(html: &Html) -> Result<String> {
         let token = html
             .select(&scraper::Selector::parse("input[name=authenticity_token]").unwrap())
@@ -72,7 +158,16 @@ impl HtmlExtractor {
     }
 
     pub fn groups(html: &Html) -> Result<Vec<Group>> {
-        let selector = scraper::Selector::parse("#adit_group_id > option").unwrap();
+        Self::group_options(html, "#adit_group_id > option")
+    }
+
+    /// Groups of the correction form, which has its own group selector.
+    pub fn correction_groups(html: &Html) -> Result<Vec<Group>> {
+        Self::group_options(html, "select[name=group_id] > option")
+    }
+
+    fn group_options(html: &Html, selector: &str) -> Result<Vec<Group>> {
+        let selector = scraper::Selector::parse(selector).unwrap();
         let options = html.select(&selector);
 
         let mut group_ids = Vec::new();
@@ -111,6 +206,438 @@ impl HtmlExtractor {
             }),
         }
     }
+
+    /// Parse the attendance table of `year`/`month`. Columns are looked up by their header, so
+    /// columns hidden by the company settings are left empty.
+    pub fn attendance_records(html: &Html, year: i32, month: u32) -> Result<Vec<AttendanceRecord>> {
+        let table = Table::find(html, "日付", "attendance table")?;
+        let date_column = table.column(&["日付"]);
+        let holiday_type_column = table.column(&["休日区分"]);
+        let clock_in_column = table.column(&["出勤時刻", "出勤"]);
+        let clock_out_column = table.column(&["退勤時刻", "退勤"]);
+        let break_time_column = table.column(&["休憩時間"]);
+        let work_time_column = table.column(&["労働時間"]);
+        let overtime_column = table.column(&["残業時間", "残業"]);
+        let notes_column = table.column(&["備考"]);
+
+        let date_re = Regex::new(r"^(\d{1,2})/(\d{1,2})").unwrap();
+        let mut records = Vec::new();
+        for row in &table.rows {
+            // Note: Rows without a date, such as the total row, are not days
+            let date_text = Table::cell(row, date_column).unwrap_or_default();
+            let Some(caps) = date_re.captures(&date_text) else {
+                continue;
+            };
+            let row_month: u32 = caps[1].parse().unwrap();
+            let row_day: u32 = caps[2].parse().unwrap();
+            // Note: A closing period can span two years, either starting in the previous year or
+            // ending in the next one, so the year is the one which puts the row nearest `month`
+            let row_year = match row_month as i32 - month as i32 {
+                diff if diff > 6 => year - 1,
+                diff if diff < -6 => year + 1,
+                _ => year,
+            };
+            let date = NaiveDate::from_ymd_opt(row_year, row_month, row_day).ok_or_else(|| {
+                JobcanError::ElementExtractError {
+                    message: format!("Invalid date found in attendance table: `{}`", &caps[0]),
+                }
+            })?;
+
+            records.push(AttendanceRecord {
+                date,
+                holiday_type: Table::cell(row, holiday_type_column),
+                clock_in: Table::cell(row, clock_in_column),
+                clock_out: Table::cell(row, clock_out_column),
+                break_time: Table::cell(row, break_time_column),
+                work_time: Table::cell(row, work_time_column),
+                overtime: Table::cell(row, overtime_column),
+                notes: Table::cell(row, notes_column),
+            });
+        }
+
+        Ok(records)
+    }
+
+    /// Parse today's stamps listed on the employee page, oldest first.
+    ///
+    /// Rows of an unknown stamp type are skipped, and no stamps are returned if the table is
+    /// missing.
+    pub fn stamp_logs(html: &Html) -> Vec<StampLog> {
+        // Note: The table is only for display, so it must not break stamping or status
+        let Ok(table) = Table::find(html, "打刻区分", "stamp log table") else {
+            return Vec::new();
+        };
+        let time_column = table.column(&["時刻", "打刻時刻"]);
+        let stamp_column = table.column(&["打刻区分"]);
+        let group_column = table.column(&["グループ"]);
+        let note_column = table.column(&["備考"]);
+
+        let time_re = Regex::new(r"^\d{1,2}:\d{2}$").unwrap();
+        let mut logs = Vec::new();
+        for row in &table.rows {
+            let Some(time) = Table::cell(row, time_column).filter(|time| time_re.is_match(time))
+            else {
+                continue;
+            };
+            let label = Table::cell(row, stamp_column).unwrap_or_default();
+            let Some(stamp) = Stamp::from_label(&label) else {
+                continue;
+            };
+
+            logs.push(StampLog {
+                time,
+                stamp,
+                group: Table::cell(row, group_column),
+                note: Table::cell(row, note_column),
+            });
+        }
+
+        logs
+    }
+
+    /// Parse the stamps of `date` listed on the correction page, oldest first.
+    pub fn corrections(html: &Html, date: NaiveDate) -> Result<Vec<Correction>> {
+        let table = Table::find(html, "打刻区分", "correction table")?;
+        let time_column = table.column(&["時刻", "打刻時刻"]);
+        let stamp_column = table.column(&["打刻区分"]);
+        let group_column = table.column(&["グループ"]);
+        let reason_column = table.column(&["申請理由", "備考"]);
+        let state_column =
+            table.required_column(&["状態", "申請状態"], "state", "correction table")?;
+
+        let time_re = Regex::new(r"^\d{1,2}:\d{2}$").unwrap();
+        let mut corrections = Vec::new();
+        for row in &table.rows {
+            let time = Table::cell(row, time_column)
+                .filter(|time| time_re.is_match(time))
+                .ok_or_else(|| JobcanError::ElementExtractError {
+                    message: "Failed to get time of correction".into(),
+                })?;
+            let label = Table::cell(row, stamp_column).unwrap_or_default();
+            let stamp =
+                Stamp::from_label(&label).ok_or_else(|| JobcanError::ElementExtractError {
+                    message: format!("Unknown stamp type found in correction table: `{}`", label),
+                })?;
+            let state_label = Table::cell(row, Some(state_column));
+            let state = ApprovalState::from_label(state_label.as_deref()).ok_or_else(|| {
+                JobcanError::ElementExtractError {
+                    message: format!(
+                        "Unknown state found in correction table: `{}`",
+                        state_label.unwrap_or_default()
+                    ),
+                }
+            })?;
+
+            corrections.push(Correction {
+                date,
+                time,
+                stamp,
+                group: Table::cell(row, group_column),
+                reason: Table::cell(row, reason_column),
+                state,
+            });
+        }
+
+        Ok(corrections)
+    }
+
+    /// Parse the balance table of the leave page, one entry per leave type.
+    pub fn leave_balances(html: &Html) -> Result<Vec<LeaveBalance>> {
+        let table = Table::find(html, "残日数", "leave balance table")?;
+        let type_column = table.column(&["休暇種別", "休暇名"]);
+        let granted_column = table.column(&["付与日数"]);
+        let used_column = table.column(&["取得日数", "使用日数"]);
+        let remaining_column = table.column(&["残日数"]);
+        let expires_column = table.column(&["有効期限"]);
+
+        let mut balances = Vec::new();
+        for row in &table.rows {
+            let leave_type =
+                Table::cell(row, type_column).ok_or_else(|| JobcanError::ElementExtractError {
+                    message: "Failed to get leave type of leave balance".into(),
+                })?;
+            balances.push(LeaveBalance {
+                leave_type,
+                granted: Table::cell(row, granted_column),
+                used: Table::cell(row, used_column),
+                remaining: Table::cell(row, remaining_column),
+                expires: Table::cell(row, expires_column),
+            });
+        }
+
+        Ok(balances)
+    }
+
+    /// Leave types of the leave application form, without the placeholder option.
+    pub fn leave_types(html: &Html) -> Result<Vec<LeaveType>> {
+        Ok(
+            Self::group_options(html, "select[name=holiday_type_id] > option")?
+                .into_iter()
+                .filter(|option| !option.id.is_empty())
+                .map(|option| LeaveType {
+                    id: option.id,
+                    name: option.name,
+                })
+                .collect(),
+        )
+    }
+
+    /// Parse the leave applications listed on the leave page, in the order shown.
+    pub fn leave_applications(html: &Html) -> Result<Vec<LeaveApplication>> {
+        let table = Table::find(html, "取得日", "leave application table")?;
+        let date_column = table.column(&["取得日"]);
+        let type_column = table.column(&["休暇種別", "休暇名"]);
+        let period_column = table.column(&["区分", "取得単位"]);
+        let reason_column = table.column(&["申請理由", "備考"]);
+        let state_column =
+            table.required_column(&["状態", "申請状態"], "state", "leave application table")?;
+
+        let mut applications = Vec::new();
+        for row in &table.rows {
+            let date_text = Table::cell(row, date_column).unwrap_or_default();
+            let date = Self::date(&date_text).ok_or_else(|| JobcanError::ElementExtractError {
+                message: format!(
+                    "Invalid date found in leave application table: `{}`",
+                    date_text
+                ),
+            })?;
+            let leave_type = Table::cell(row, type_column).unwrap_or_default();
+            let period_label = Table::cell(row, period_column);
+            let period = LeavePeriod::from_label(period_label.as_deref()).ok_or_else(|| {
+                JobcanError::ElementExtractError {
+                    message: format!(
+                        "Unknown period found in leave application table: `{}`",
+                        period_label.unwrap_or_default()
+                    ),
+                }
+            })?;
+            let state_label = Table::cell(row, Some(state_column));
+            let state = ApprovalState::from_label(state_label.as_deref()).ok_or_else(|| {
+                JobcanError::ElementExtractError {
+                    message: format!(
+                        "Unknown state found in leave application table: `{}`",
+                        state_label.unwrap_or_default()
+                    ),
+                }
+            })?;
+
+            applications.push(LeaveApplication {
+                date,
+                leave_type,
+                period,
+                reason: Table::cell(row, reason_column),
+                state,
+            });
+        }
+
+        Ok(applications)
+    }
+
+    /// Parse the overtime applications listed on the overtime page, in the order shown.
+    pub fn overtime_applications(html: &Html) -> Result<Vec<OvertimeApplication>> {
+        let table = Table::find(html, "終了予定時刻", "overtime application table")?;
+        let date_column = table.column(&["対象日", "日付"]);
+        let end_time_column = table.column(&["終了予定時刻"]);
+        let overtime_column = table.column(&["残業時間"]);
+        let reason_column = table.column(&["申請理由", "備考"]);
+        let state_column =
+            table.required_column(&["状態", "申請状態"], "state", "overtime application table")?;
+
+        let mut applications = Vec::new();
+        for row in &table.rows {
+            let date_text = Table::cell(row, date_column).unwrap_or_default();
+            let date = Self::date(&date_text).ok_or_else(|| JobcanError::ElementExtractError {
+                message: format!(
+                    "Invalid date found in overtime application table: `{}`",
+                    date_text
+                ),
+            })?;
+            let end_time = Table::cell(row, end_time_column).ok_or_else(|| {
+                JobcanError::ElementExtractError {
+                    message: "Failed to get end time of overtime application".into(),
+                }
+            })?;
+            let state_label = Table::cell(row, Some(state_column));
+            let state = ApprovalState::from_label(state_label.as_deref()).ok_or_else(|| {
+                JobcanError::ElementExtractError {
+                    message: format!(
+                        "Unknown state found in overtime application table: `{}`",
+                        state_label.unwrap_or_default()
+                    ),
+                }
+            })?;
+
+            applications.push(OvertimeApplication {
+                date,
+                end_time,
+                overtime: Table::cell(row, overtime_column),
+                reason: Table::cell(row, reason_column),
+                state,
+            });
+        }
+
+        Ok(applications)
+    }
+
+    /// Parse the application history (申請一覧), in the order shown.
+    pub fn applications(html: &Html) -> Result<Vec<Application>> {
+        let table = Table::find(html, "申請種別", "application table")?;
+        let kind_column = table.column(&["申請種別"]);
+        let date_column = table.column(&["対象日"]);
+        let submitted_at_column = table.column(&["申請日時", "申請日"]);
+        let approver_column = table.column(&["承認者"]);
+        let state_column =
+            table.required_column(&["状態", "申請状態"], "state", "application table")?;
+
+        let mut applications = Vec::new();
+        for row in &table.rows {
+            let label = Table::cell(row, kind_column).unwrap_or_default();
+            let date_text = Table::cell(row, date_column).unwrap_or_default();
+            let date = Self::date(&date_text).ok_or_else(|| JobcanError::ElementExtractError {
+                message: format!("Invalid date found in application table: `{}`", date_text),
+            })?;
+            let state_label = Table::cell(row, Some(state_column));
+            let state = ApprovalState::from_label(state_label.as_deref()).ok_or_else(|| {
+                JobcanError::ElementExtractError {
+                    message: format!(
+                        "Unknown state found in application table: `{}`",
+                        state_label.unwrap_or_default()
+                    ),
+                }
+            })?;
+
+            applications.push(Application {
+                kind: ApplicationKind::from_label(&label),
+                label,
+                date,
+                submitted_at: Table::cell(row, submitted_at_column)
+                    .and_then(|text| Self::date_time(&text)),
+                approver: Table::cell(row, approver_column),
+                state,
+            });
+        }
+
+        Ok(applications)
+    }
+
+    /// Parse the shift page (シフト表) into the planned shift of each day.
+    pub fn shifts(html: &Html) -> Result<Vec<Shift>> {
+        let table = Table::find(html, "開始予定", "shift table")?;
+        let date_column = table.column(&["日付"]);
+        let start_column = table.column(&["開始予定"]);
+        let end_column = table.column(&["終了予定"]);
+        let group_column = table.column(&["グループ"]);
+
+        let mut shifts = Vec::new();
+        for row in &table.rows {
+            let date_text = Table::cell(row, date_column).unwrap_or_default();
+            let date = Self::date(&date_text).ok_or_else(|| JobcanError::ElementExtractError {
+                message: format!("Invalid date found in shift table: `{}`", date_text),
+            })?;
+
+            shifts.push(Shift {
+                date,
+                start: Table::cell(row, start_column),
+                end: Table::cell(row, end_column),
+                group: Table::cell(row, group_column),
+            });
+        }
+
+        Ok(shifts)
+    }
+
+    /// Date and time in `YYYY/MM/DD HH:MM` or `YYYY-MM-DD HH:MM`, e.g. `2024/01/15 18:40`.
+    fn date_time(text: &str) -> Option<NaiveDateTime> {
+        let re =
+            Regex::new(r"^(\d{4})[/-](\d{1,2})[/-](\d{1,2})(?:\([^)]*\))?\s+(\d{1,2}):(\d{2})")
+                .unwrap();
+        let caps = re.captures(text)?;
+        NaiveDate::from_ymd_opt(
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        )?
+        .and_hms_opt(caps[4].parse().ok()?, caps[5].parse().ok()?, 0)
+    }
+
+    /// Date at the start of `text` in `YYYY/MM/DD` or `YYYY-MM-DD`, e.g. `2024/01/15(月)`.
+    fn date(text: &str) -> Option<NaiveDate> {
+        let re = Regex::new(r"^(\d{4})[/-](\d{1,2})[/-](\d{1,2})").unwrap();
+        let caps = re.captures(text)?;
+        NaiveDate::from_ymd_opt(
+            caps[1].parse().ok()?,
+            caps[2].parse().ok()?,
+            caps[3].parse().ok()?,
+        )
+    }
+
+    /// Error message shown at the top of a form, e.g. when a required field is empty.
+    pub fn alert(html: &Html) -> Option<String> {
+        let selector = scraper::Selector::parse(".alert-danger, .error").unwrap();
+        html.select(&selector)
+            .map(Table::text)
+            .find(|text| !text.is_empty())
+    }
+}
+
+/// Cell texts of a table whose columns are identified by their headers.
+struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// Find the first `table.jbc-table` which has a column named `key_header`.
+    fn find(html: &Html, key_header: &str, description: &str) -> Result<Table> {
+        let table_selector = scraper::Selector::parse("table.jbc-table").unwrap();
+        let header_selector = scraper::Selector::parse("thead th").unwrap();
+        let row_selector = scraper::Selector::parse("tbody > tr").unwrap();
+        let cell_selector = scraper::Selector::parse("td").unwrap();
+
+        html.select(&table_selector)
+            .map(|table| Table {
+                headers: table.select(&header_selector).map(Self::text).collect(),
+                rows: table
+                    .select(&row_selector)
+                    .map(|row| row.select(&cell_selector).map(Self::text).collect())
+                    .collect(),
+            })
+            .find(|table| table.headers.iter().any(|h| h == key_header))
+            .ok_or_else(|| JobcanError::ElementExtractError {
+                message: format!("Failed to find {}", description),
+            })
+    }
+
+    fn column(&self, names: &[&str]) -> Option<usize> {
+        self.headers
+            .iter()
+            .position(|h| names.contains(&h.as_str()))
+    }
+
+    /// Same as [`Table::column`], but fails if the table has no such column.
+    fn required_column(&self, names: &[&str], name: &str, description: &str) -> Result<usize> {
+        self.column(names)
+            .ok_or_else(|| JobcanError::ElementExtractError {
+                message: format!("Failed to find {} column of {}", name, description),
+            })
+    }
+
+    /// Text of the cell, or `None` if the column or the value is missing.
+    fn cell(row: &[String], column: Option<usize>) -> Option<String> {
+        column
+            .and_then(|i| row.get(i))
+            .filter(|text| !text.is_empty() && text.as_str() != "-")
+            .cloned()
+    }
+
+    fn text(element: ElementRef) -> String {
+        element
+            .text()
+            .collect::<String>()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
 }
 
 #[cfg(test)]
@@ -127,7 +654,7 @@ mod tests {
                     <input name="authenticity_token" value="token">
                 </body>
             </html>"""#;
-        let html = scraper::Html::parse_document(&body);
+        let html = scraper::Html::parse_document(body);
 
         // Act
         let token = HtmlExtractor::authenticity_token(&html);
@@ -186,7 +713,7 @@ mod tests {
                     <input name="token" value="token">
                 </body>
             </html>"""#;
-        let html = scraper::Html::parse_document(&body);
+        let html = scraper::Html::parse_document(body);
 
         // Act
         let token = HtmlExtractor::token(&html);
@@ -208,7 +735,7 @@ mod tests {
                     </select>
                 </body>
             </html>"""#;
-        let html = scraper::Html::parse_document(&body);
+        let html = scraper::Html::parse_document(body);
         let expected = vec![
             Group {
                 id: "1".to_string(),
@@ -238,7 +765,7 @@ mod tests {
                     </select>
                 </body>
             </html>"""#;
-        let html = scraper::Html::parse_document(&body);
+        let html = scraper::Html::parse_document(body);
 
         // Act
         let group_ids = HtmlExtractor::groups(&html);
@@ -247,6 +774,110 @@ mod tests {
         assert!(group_ids.unwrap() == Vec::<Group>::new());
     }
 
+    fn groups(names: &[&str]) -> Vec<Group> {
+        names
+            .iter()
+            .enumerate()
+            .map(|(i, name)| Group {
+                id: (i + 1).to_string(),
+                name: name.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn find_group_by_id() {
+        // Arrange
+        let groups = groups(&["Head Office", "Branch Office"]);
+
+        // Act
+        let group = Group::find_by_id(&groups, "2");
+
+        // Assert
+        assert!(group.unwrap().name() == "Branch Office");
+    }
+
+    #[test]
+    fn find_group_by_unknown_id() {
+        // Arrange
+        let groups = groups(&["Head Office", "Branch Office"]);
+
+        // Act
+        let group = Group::find_by_id(&groups, "9");
+
+        // Assert
+        assert!(
+            group.unwrap_err().to_string()
+                == "Group ID `9` is not one of your groups. Available groups: Head Office (1), Branch Office (2)"
+        );
+    }
+
+    #[test]
+    fn find_group_by_exact_name() {
+        // Arrange
+        let groups = groups(&["Office", "office", "Office Annex"]);
+
+        // Act
+        let group = Group::find_by_name(&groups, "Office");
+
+        // Assert
+        assert!(group.unwrap().id() == "1");
+    }
+
+    #[test]
+    fn find_group_by_name_ignoring_case() {
+        // Arrange
+        let groups = groups(&["Head Office", "Branch Office"]);
+
+        // Act
+        let group = Group::find_by_name(&groups, "branch office");
+
+        // Assert
+        assert!(group.unwrap().id() == "2");
+    }
+
+    #[test]
+    fn find_group_by_unique_prefix() {
+        // Arrange
+        let groups = groups(&["Head Office", "Branch Office"]);
+
+        // Act
+        let group = Group::find_by_name(&groups, "bra");
+
+        // Assert
+        assert!(group.unwrap().id() == "2");
+    }
+
+    #[test]
+    fn find_group_by_ambiguous_prefix() {
+        // Arrange
+        let groups = groups(&["Branch Osaka", "Branch Tokyo", "Head Office"]);
+
+        // Act
+        let group = Group::find_by_name(&groups, "branch");
+
+        // Assert
+        assert!(
+            group.unwrap_err().to_string()
+                == "Group `branch` is ambiguous. Available groups: Branch Osaka (1), Branch Tokyo (2)"
+        );
+    }
+
+    #[test]
+    fn find_group_by_unknown_name() {
+        // Arrange
+        let groups = groups(&["Head Office", "Branch Office"]);
+
+        // Act
+        let group = Group::find_by_name(&groups, "Remote");
+
+        // Assert
+        assert!(
+            group.unwrap_err().to_string()
+                == "Group `Remote` is not found. Available groups: Head Office (1), Branch Office (2)"
+        );
+    }
+
     #[test]
     fn default_group_id_with_expected_text() {
         // Arrange
@@ -264,7 +895,7 @@ mod tests {
         let status = HtmlExtractor::default_group_id(body).unwrap();
 
         // Assert
-        assert!(status == "1".to_string());
+        assert!(status == "1");
     }
 
     #[test]
@@ -285,4 +916,776 @@ mod tests {
         // Assert
         assert!(status.is_err());
     }
+
+    #[test]
+    fn employee_page() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head>
+                    <script>
+                        var current_status = "working";
+                        var defaultAditGroupId = 2;
+                    </script>
+                </head>
+                <body>
+                    <input name="token" value="token">
+                    <select id="adit_group_id">
+                        <option value="1">Head Office</option>
+                        <option value="2">Branch Office</option>
+                    </select>
+                    <table class="table jbc-table">
+                        <thead><tr><th>時刻</th><th>打刻区分</th></tr></thead>
+                        <tbody><tr><td>09:00</td><td>出勤</td></tr></tbody>
+                    </table>
+                </body>
+            </html>"""#;
+
+        // Act
+        let page = HtmlExtractor::employee_page(body).unwrap();
+
+        // Assert
+        assert!(page.token() == "token");
+        assert!(page.working_status() == WorkingStatus::Working);
+        assert!(page.groups().len() == 2);
+        assert!(page.default_group_id() == "2");
+        assert!(page.stamp_logs().len() == 1);
+        assert!(page.stamp_logs()[0].stamp() == Stamp::ClockIn);
+    }
+
+    #[test]
+    fn attendance_records() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead>
+                            <tr>
+                                <th>日付</th>
+                                <th>休日区分</th>
+                                <th>出勤時刻</th>
+                                <th>退勤時刻</th>
+                                <th>労働時間</th>
+                                <th>残業</th>
+                                <th>休憩時間</th>
+                                <th>備考</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <td>12/31(日)</td>
+                                <td>法定休日</td>
+                                <td></td>
+                                <td></td>
+                                <td></td>
+                                <td></td>
+                                <td></td>
+                                <td></td>
+                            </tr>
+                            <tr>
+                                <td>01/04(木)</td>
+                                <td></td>
+                                <td>09:02</td>
+                                <td>25:10</td>
+                                <td>15:08</td>
+                                <td>07:08</td>
+                                <td>01:00</td>
+                                <td>打刻漏れ</td>
+                            </tr>
+                            <tr>
+                                <td>合計</td>
+                                <td></td>
+                                <td></td>
+                                <td></td>
+                                <td>15:08</td>
+                                <td>07:08</td>
+                                <td>01:00</td>
+                                <td></td>
+                            </tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let expected = vec![
+            AttendanceRecord {
+                date: NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(),
+                holiday_type: Some("法定休日".into()),
+                clock_in: None,
+                clock_out: None,
+                break_time: None,
+                work_time: None,
+                overtime: None,
+                notes: None,
+            },
+            AttendanceRecord {
+                date: NaiveDate::from_ymd_opt(2024, 1, 4).unwrap(),
+                holiday_type: None,
+                clock_in: Some("09:02".into()),
+                clock_out: Some("25:10".into()),
+                break_time: Some("01:00".into()),
+                work_time: Some("15:08".into()),
+                overtime: Some("07:08".into()),
+                notes: Some("打刻漏れ".into()),
+            },
+        ];
+
+        // Act
+        let records = HtmlExtractor::attendance_records(&html, 2024, 1);
+
+        // Assert
+        assert!(records.unwrap() == expected);
+    }
+
+    #[test]
+    fn attendance_records_of_period_ending_in_next_month() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead><tr><th>日付</th><th>出勤時刻</th></tr></thead>
+                        <tbody>
+                            <tr><td>01/21(日)</td><td>09:00</td></tr>
+                            <tr><td>02/20(火)</td><td>09:00</td></tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let records = HtmlExtractor::attendance_records(&html, 2024, 1).unwrap();
+
+        // Assert
+        assert!(records.len() == 2);
+        assert!(records[0].date == NaiveDate::from_ymd_opt(2024, 1, 21).unwrap());
+        assert!(records[1].date == NaiveDate::from_ymd_opt(2024, 2, 20).unwrap());
+    }
+
+    #[test]
+    fn attendance_records_of_period_ending_in_next_year() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead><tr><th>日付</th><th>出勤時刻</th></tr></thead>
+                        <tbody>
+                            <tr><td>12/21(木)</td><td>09:00</td></tr>
+                            <tr><td>01/20(土)</td><td>09:00</td></tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let records = HtmlExtractor::attendance_records(&html, 2023, 12).unwrap();
+
+        // Assert
+        assert!(records.len() == 2);
+        assert!(records[0].date == NaiveDate::from_ymd_opt(2023, 12, 21).unwrap());
+        assert!(records[1].date == NaiveDate::from_ymd_opt(2024, 1, 20).unwrap());
+    }
+
+    #[test]
+    fn attendance_records_without_table() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead><tr><th>グループ</th></tr></thead>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let records = HtmlExtractor::attendance_records(&html, 2024, 1);
+
+        // Assert
+        assert!(records.is_err());
+    }
+
+    #[test]
+    fn stamp_logs() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead>
+                            <tr>
+                                <th>時刻</th>
+                                <th>打刻区分</th>
+                                <th>グループ</th>
+                                <th>備考</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <td>09:02</td>
+                                <td>出勤</td>
+                                <td>Head Office</td>
+                                <td></td>
+                            </tr>
+                            <tr>
+                                <td>12:00</td>
+                                <td>休憩開始</td>
+                                <td>Head Office</td>
+                                <td>lunch</td>
+                            </tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let expected = vec![
+            StampLog {
+                time: "09:02".into(),
+                stamp: Stamp::ClockIn,
+                group: Some("Head Office".into()),
+                note: None,
+            },
+            StampLog {
+                time: "12:00".into(),
+                stamp: Stamp::StartBreak,
+                group: Some("Head Office".into()),
+                note: Some("lunch".into()),
+            },
+        ];
+
+        // Act
+        let logs = HtmlExtractor::stamp_logs(&html);
+
+        // Assert
+        assert!(logs == expected);
+    }
+
+    #[test]
+    fn stamp_logs_without_stamp() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead><tr><th>時刻</th><th>打刻区分</th></tr></thead>
+                        <tbody></tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let logs = HtmlExtractor::stamp_logs(&html);
+
+        // Assert
+        assert!(logs.is_empty());
+    }
+
+    #[test]
+    fn stamp_logs_with_unknown_stamp_type() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead><tr><th>時刻</th><th>打刻区分</th></tr></thead>
+                        <tbody>
+                            <tr><td>09:00</td><td>入室</td></tr>
+                            <tr><td>09:01</td><td>出勤</td></tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let logs = HtmlExtractor::stamp_logs(&html);
+
+        // Assert
+        assert!(logs.len() == 1);
+        assert!(logs[0].stamp() == Stamp::ClockIn);
+    }
+
+    #[test]
+    fn stamp_logs_without_table() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body></body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let logs = HtmlExtractor::stamp_logs(&html);
+
+        // Assert
+        assert!(logs.is_empty());
+    }
+
+    #[test]
+    fn corrections() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <input type="hidden" name="token" value="token">
+                    <select name="group_id"><option value="1">Head Office</option></select>
+                    <table class="table jbc-table">
+                        <thead>
+                            <tr>
+                                <th>時刻</th>
+                                <th>打刻区分</th>
+                                <th>グループ</th>
+                                <th>申請理由</th>
+                                <th>状態</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <td>09:00</td>
+                                <td>出勤</td>
+                                <td>Head Office</td>
+                                <td></td>
+                                <td></td>
+                            </tr>
+                            <tr>
+                                <td>18:30</td>
+                                <td>退勤</td>
+                                <td>Head Office</td>
+                                <td>Forgot to clock out</td>
+                                <td>申請中</td>
+                            </tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let expected = vec![
+            Correction {
+                date,
+                time: "09:00".into(),
+                stamp: Stamp::ClockIn,
+                group: Some("Head Office".into()),
+                reason: None,
+                state: ApprovalState::Applied,
+            },
+            Correction {
+                date,
+                time: "18:30".into(),
+                stamp: Stamp::ClockOut,
+                group: Some("Head Office".into()),
+                reason: Some("Forgot to clock out".into()),
+                state: ApprovalState::Pending,
+            },
+        ];
+
+        // Act
+        let corrections = HtmlExtractor::corrections(&html, date);
+        let groups = HtmlExtractor::correction_groups(&html);
+
+        // Assert
+        assert!(corrections.unwrap() == expected);
+        assert!(
+            groups.unwrap()
+                == vec![Group {
+                    id: "1".into(),
+                    name: "Head Office".into()
+                }]
+        );
+    }
+
+    #[test]
+    fn corrections_with_unknown_state() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead><tr><th>時刻</th><th>打刻区分</th><th>状態</th></tr></thead>
+                        <tbody><tr><td>18:30</td><td>退勤</td><td>保留</td></tr></tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        // Act
+        let corrections = HtmlExtractor::corrections(&html, date);
+
+        // Assert
+        assert!(corrections.is_err());
+    }
+
+    #[test]
+    fn corrections_without_state_column() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead><tr><th>時刻</th><th>打刻区分</th></tr></thead>
+                        <tbody><tr><td>18:30</td><td>退勤</td></tr></tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        // Act
+        let corrections = HtmlExtractor::corrections(&html, date);
+
+        // Assert
+        assert!(matches!(
+            corrections,
+            Err(JobcanError::ElementExtractError { .. })
+        ));
+    }
+
+    #[test]
+    fn corrections_with_empty_state() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead><tr><th>時刻</th><th>打刻区分</th><th>状態</th></tr></thead>
+                        <tbody><tr><td>18:30</td><td>退勤</td><td></td></tr></tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        // Act
+        let corrections = HtmlExtractor::corrections(&html, date).unwrap();
+
+        // Assert
+        assert!(corrections.len() == 1);
+        assert!(corrections[0].state == ApprovalState::Applied);
+    }
+
+    #[test]
+    fn leave_balances() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead>
+                            <tr>
+                                <th>休暇種別</th>
+                                <th>付与日数</th>
+                                <th>取得日数</th>
+                                <th>残日数</th>
+                                <th>有効期限</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <td>有給休暇</td>
+                                <td>20日</td>
+                                <td>7.5日</td>
+                                <td>12.5日</td>
+                                <td>2025/03/31</td>
+                            </tr>
+                            <tr>
+                                <td>特別休暇</td>
+                                <td>-</td>
+                                <td>0日</td>
+                                <td>3日</td>
+                                <td></td>
+                            </tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let expected = vec![
+            LeaveBalance {
+                leave_type: "有給休暇".into(),
+                granted: Some("20日".into()),
+                used: Some("7.5日".into()),
+                remaining: Some("12.5日".into()),
+                expires: Some("2025/03/31".into()),
+            },
+            LeaveBalance {
+                leave_type: "特別休暇".into(),
+                granted: None,
+                used: Some("0日".into()),
+                remaining: Some("3日".into()),
+                expires: None,
+            },
+        ];
+
+        // Act
+        let balances = HtmlExtractor::leave_balances(&html);
+
+        // Assert
+        assert!(balances.unwrap() == expected);
+    }
+
+    #[test]
+    fn leave_types_and_applications() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <select name="holiday_type_id">
+                        <option value="">選択してください</option>
+                        <option value="1">有給休暇</option>
+                    </select>
+                    <table class="table jbc-table">
+                        <thead>
+                            <tr>
+                                <th>取得日</th>
+                                <th>休暇種別</th>
+                                <th>区分</th>
+                                <th>申請理由</th>
+                                <th>状態</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <td>2024/01/15(月)</td>
+                                <td>有給休暇</td>
+                                <td>午前休</td>
+                                <td>Hospital</td>
+                                <td>承認済</td>
+                            </tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let expected = vec![LeaveApplication {
+            date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+            leave_type: "有給休暇".into(),
+            period: LeavePeriod::Morning,
+            reason: Some("Hospital".into()),
+            state: ApprovalState::Approved,
+        }];
+
+        // Act
+        let leave_types = HtmlExtractor::leave_types(&html);
+        let applications = HtmlExtractor::leave_applications(&html);
+
+        // Assert
+        assert!(
+            leave_types.unwrap()
+                == vec![LeaveType {
+                    id: "1".into(),
+                    name: "有給休暇".into()
+                }]
+        );
+        assert!(applications.unwrap() == expected);
+    }
+
+    #[test]
+    fn overtime_applications() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead>
+                            <tr>
+                                <th>対象日</th>
+                                <th>終了予定時刻</th>
+                                <th>残業時間</th>
+                                <th>申請理由</th>
+                                <th>状態</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <td>2024/01/15(月)</td>
+                                <td>20:00</td>
+                                <td>2:00</td>
+                                <td>Release</td>
+                                <td>申請中</td>
+                            </tr>
+                            <tr>
+                                <td>2024/01/16(火)</td>
+                                <td>19:30</td>
+                                <td>1:30</td>
+                                <td>Review</td>
+                                <td>却下</td>
+                            </tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let applications = HtmlExtractor::overtime_applications(&html).unwrap();
+
+        // Assert
+        assert!(applications.len() == 2);
+        assert!(applications[0].date == NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert!(applications[0].end_time == "20:00");
+        assert!(applications[0].overtime.as_deref() == Some("2:00"));
+        assert!(applications[0].state == ApprovalState::Pending);
+        assert!(applications[1].state == ApprovalState::Rejected);
+    }
+
+    #[test]
+    fn shifts() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead>
+                            <tr>
+                                <th>日付</th>
+                                <th>開始予定</th>
+                                <th>終了予定</th>
+                                <th>グループ</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <td>2024/01/15(月)</td>
+                                <td>09:00</td>
+                                <td>18:00</td>
+                                <td>Head Office</td>
+                            </tr>
+                            <tr>
+                                <td>2024/01/16(火)</td>
+                                <td>22:00</td>
+                                <td>31:00</td>
+                                <td>Warehouse</td>
+                            </tr>
+                            <tr>
+                                <td>2024/01/17(水)</td>
+                                <td>-</td>
+                                <td>-</td>
+                                <td></td>
+                            </tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let shifts = HtmlExtractor::shifts(&html).unwrap();
+
+        // Assert
+        assert!(shifts.len() == 3);
+        assert!(shifts[0].date == NaiveDate::from_ymd_opt(2024, 1, 15).unwrap());
+        assert!(shifts[0].start.as_deref() == Some("09:00"));
+        assert!(shifts[0].group.as_deref() == Some("Head Office"));
+        assert!(shifts[1].end.as_deref() == Some("31:00"));
+        assert!(shifts[2].start.is_none() && shifts[2].end.is_none() && shifts[2].group.is_none());
+    }
+
+    #[test]
+    fn applications() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <table class="table jbc-table">
+                        <thead>
+                            <tr>
+                                <th>申請種別</th>
+                                <th>対象日</th>
+                                <th>申請日時</th>
+                                <th>承認者</th>
+                                <th>状態</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            <tr>
+                                <td>休暇申請</td>
+                                <td>2024/01/15(月)</td>
+                                <td>2024/01/10 09:30</td>
+                                <td>山田 太郎</td>
+                                <td>承認済</td>
+                            </tr>
+                            <tr>
+                                <td>打刻修正申請</td>
+                                <td>2024/01/12(金)</td>
+                                <td>-</td>
+                                <td></td>
+                                <td>申請中</td>
+                            </tr>
+                        </tbody>
+                    </table>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+        let expected = vec![
+            Application {
+                kind: ApplicationKind::Leave,
+                label: "休暇申請".into(),
+                date: NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(),
+                submitted_at: NaiveDate::from_ymd_opt(2024, 1, 10)
+                    .unwrap()
+                    .and_hms_opt(9, 30, 0),
+                approver: Some("山田 太郎".into()),
+                state: ApprovalState::Approved,
+            },
+            Application {
+                kind: ApplicationKind::Correction,
+                label: "打刻修正申請".into(),
+                date: NaiveDate::from_ymd_opt(2024, 1, 12).unwrap(),
+                submitted_at: None,
+                approver: None,
+                state: ApprovalState::Pending,
+            },
+        ];
+
+        // Act
+        let applications = HtmlExtractor::applications(&html);
+
+        // Assert
+        assert!(applications.unwrap() == expected);
+    }
+
+    #[test]
+    fn alert() {
+        // Arrange
+        let body = r#"""
+            <html>
+                <head></head>
+                <body>
+                    <div class="alert alert-danger">
+                        申請理由を入力してください
+                    </div>
+                </body>
+            </html>"""#;
+        let html = scraper::Html::parse_document(body);
+
+        // Act
+        let alert = HtmlExtractor::alert(&html);
+
+        // Assert
+        assert!(alert.as_deref() == Some("申請理由を入力してください"));
+    }
 }
@@ -0,0 +1,223 @@
+//! Japanese public holidays (国民の祝日) from 2000 to 2099, computed from the rules of the
+//! holiday law instead of a table which has to be updated every year.
+
+use chrono::{Datelike, Days, NaiveDate, Weekday};
+
+pub const FIRST_YEAR: i32 = 2000;
+pub const LAST_YEAR: i32 = 2099;
+
+/// Name of the holiday on `date`, or `None` on a working day or out of the supported years.
+pub fn holiday(date: NaiveDate) -> Option<&'static str> {
+    if !(FIRST_YEAR..=LAST_YEAR).contains(&date.year()) {
+        return None;
+    }
+    if let Some(name) = national_holiday(date) {
+        return Some(name);
+    }
+    if is_substitute_holiday(date) {
+        return Some("振替休日");
+    }
+    if is_citizens_holiday(date) {
+        return Some("国民の休日");
+    }
+    None
+}
+
+/// Holidays named by the law, without substitute and citizens' holidays.
+fn national_holiday(date: NaiveDate) -> Option<&'static str> {
+    let year = date.year();
+    let (month, day) = (date.month(), date.day());
+    let monday = |n| date.weekday() == Weekday::Mon && (day - 1) / 7 + 1 == n;
+
+    // Note: The Olympics moved three holidays in 2020 and 2021
+    let (marine_day, sports_day, mountain_day) = match year {
+        2020 => ((7, 23), (7, 24), (8, 10)),
+        2021 => ((7, 22), (7, 23), (8, 8)),
+        _ => ((0, 0), (0, 0), (0, 0)),
+    };
+    let moved = |(m, d)| (month, day) == (m, d);
+
+    match (month, day) {
+        (1, 1) => Some("元日"),
+        (1, _) if monday(2) => Some("成人の日"),
+        (2, 11) => Some("建国記念の日"),
+        (2, 23) if year >= 2020 => Some("天皇誕生日"),
+        (3, _) if day == vernal_equinox(year) => Some("春分の日"),
+        (4, 29) if year >= 2007 => Some("昭和の日"),
+        (4, 29) => Some("みどりの日"),
+        (5, 1) if year == 2019 => Some("天皇の即位の日"),
+        (5, 3) => Some("憲法記念日"),
+        (5, 4) if year >= 2007 => Some("みどりの日"),
+        (5, 5) => Some("こどもの日"),
+        _ if (2020..=2021).contains(&year) && moved(marine_day) => Some("海の日"),
+        _ if (2020..=2021).contains(&year) && moved(sports_day) => Some("スポーツの日"),
+        _ if (2020..=2021).contains(&year) && moved(mountain_day) => Some("山の日"),
+        (7, 20) if year < 2003 => Some("海の日"),
+        (7, _) if year >= 2003 && !(2020..=2021).contains(&year) && monday(3) => Some("海の日"),
+        (8, 11) if year >= 2016 && !(2020..=2021).contains(&year) => Some("山の日"),
+        (9, 15) if year < 2003 => Some("敬老の日"),
+        (9, _) if year >= 2003 && monday(3) => Some("敬老の日"),
+        (9, _) if day == autumnal_equinox(year) => Some("秋分の日"),
+        (10, _) if !(2020..=2021).contains(&year) && monday(2) => Some(if year >= 2020 {
+            "スポーツの日"
+        } else {
+            "体育の日"
+        }),
+        (10, 22) if year == 2019 => Some("即位礼正殿の儀の日"),
+        (11, 3) => Some("文化の日"),
+        (11, 23) => Some("勤労感謝の日"),
+        (12, 23) if year <= 2018 => Some("天皇誕生日"),
+        _ => None,
+    }
+}
+
+/// A day off after a holiday on Sunday, or after consecutive holidays from a Sunday.
+fn is_substitute_holiday(date: NaiveDate) -> bool {
+    let mut day = date;
+    while let Some(previous) = day.checked_sub_days(Days::new(1)) {
+        if national_holiday(previous).is_none() {
+            return false;
+        }
+        if previous.weekday() == Weekday::Sun {
+            return true;
+        }
+        day = previous;
+    }
+    false
+}
+
+/// A working day between two holidays, e.g. 2026-09-22.
+fn is_citizens_holiday(date: NaiveDate) -> bool {
+    let (Some(previous), Some(next)) = (
+        date.checked_sub_days(Days::new(1)),
+        date.checked_add_days(Days::new(1)),
+    ) else {
+        return false;
+    };
+    date.weekday() != Weekday::Sun
+        && national_holiday(previous).is_some()
+        && national_holiday(next).is_some()
+}
+
+/// Day of March, by the approximation of the National Astronomical Observatory for 1980-2099.
+fn vernal_equinox(year: i32) -> u32 {
+    equinox(20.8431, year)
+}
+
+/// Day of September, by the same approximation as [`vernal_equinox`].
+fn autumnal_equinox(year: i32) -> u32 {
+    equinox(23.2488, year)
+}
+
+fn equinox(base: f64, year: i32) -> u32 {
+    let years = f64::from(year - 1980);
+    (base + 0.242194 * years - (years / 4.0).floor()).floor() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holidays_of(year: i32) -> Vec<(String, &'static str)> {
+        let mut date = NaiveDate::from_ymd_opt(year, 1, 1).unwrap();
+        let mut holidays = Vec::new();
+        while date.year() == year {
+            if let Some(name) = holiday(date) {
+                holidays.push((date.format("%m-%d").to_string(), name));
+            }
+            date = date.succ_opt().unwrap();
+        }
+        holidays
+    }
+
+    #[test]
+    fn holidays_of_2024() {
+        // Act
+        let holidays = holidays_of(2024);
+
+        // Assert
+        let expected = [
+            ("01-01", "元日"),
+            ("01-08", "成人の日"),
+            ("02-11", "建国記念の日"),
+            ("02-12", "振替休日"),
+            ("02-23", "天皇誕生日"),
+            ("03-20", "春分の日"),
+            ("04-29", "昭和の日"),
+            ("05-03", "憲法記念日"),
+            ("05-04", "みどりの日"),
+            ("05-05", "こどもの日"),
+            ("05-06", "振替休日"),
+            ("07-15", "海の日"),
+            ("08-11", "山の日"),
+            ("08-12", "振替休日"),
+            ("09-16", "敬老の日"),
+            ("09-22", "秋分の日"),
+            ("09-23", "振替休日"),
+            ("10-14", "スポーツの日"),
+            ("11-03", "文化の日"),
+            ("11-04", "振替休日"),
+            ("11-23", "勤労感謝の日"),
+        ];
+        assert!(holidays.iter().map(|(d, n)| (d.as_str(), *n)).eq(expected));
+    }
+
+    #[test]
+    fn holidays_of_2019_around_the_enthronement() {
+        // Act
+        let holidays = holidays_of(2019);
+
+        // Assert
+        let golden_week: Vec<&str> = holidays
+            .iter()
+            .filter(|(date, _)| date.as_str() >= "04-27" && date.as_str() <= "05-07")
+            .map(|(date, _)| date.as_str())
+            .collect();
+        assert!(
+            golden_week == ["04-29", "04-30", "05-01", "05-02", "05-03", "05-04", "05-05", "05-06"]
+        );
+        assert!(holidays.contains(&("10-22".into(), "即位礼正殿の儀の日")));
+        assert!(!holidays
+            .iter()
+            .any(|(date, _)| date == "12-23" || date == "02-23"));
+    }
+
+    #[test]
+    fn holidays_moved_by_the_olympics() {
+        // Act
+        let holidays = holidays_of(2021);
+
+        // Assert
+        assert!(holidays.contains(&("07-22".into(), "海の日")));
+        assert!(holidays.contains(&("07-23".into(), "スポーツの日")));
+        assert!(holidays.contains(&("08-08".into(), "山の日")));
+        assert!(holidays.contains(&("08-09".into(), "振替休日")));
+        assert!(!holidays
+            .iter()
+            .any(|(date, _)| date == "07-19" || date == "10-11"));
+    }
+
+    #[test]
+    fn citizens_holiday_between_holidays() {
+        // Arrange
+        let date = NaiveDate::from_ymd_opt(2026, 9, 22).unwrap();
+
+        // Act
+        let name = holiday(date);
+
+        // Assert
+        assert!(name == Some("国民の休日"));
+    }
+
+    #[test]
+    fn working_day() {
+        // Arrange
+        let date = NaiveDate::from_ymd_opt(2024, 1, 9).unwrap();
+
+        // Act
+        let name = holiday(date);
+
+        // Assert
+        assert!(name.is_none());
+    }
+}
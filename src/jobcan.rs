@@ -1,40 +1,106 @@
-use reqwest::Response;
+use std::{path::PathBuf, sync::Arc, time::Duration};
+
+use chrono::Datelike;
+use reqwest::{RequestBuilder, Response, Url};
+use secrecy::{ExposeSecret, SecretString};
 
 use crate::{
     account::Account,
+    application::Application,
+    attendance::AttendanceRecord,
+    clock_time::ClockTime,
+    correction::{Correction, CorrectionRequest},
+    employee_page::EmployeePage,
     error::JobcanError,
     html_extractor::{Group, HtmlExtractor},
+    leave::{LeaveApplication, LeaveBalance, LeaveRequest, LeaveType},
+    overtime::{OvertimeApplication, OvertimeRequest},
+    retry::RetryPolicy,
+    session::Session,
+    shift::Shift,
     stamp::{self, Stamp},
+    stamp_log::StampLog,
     working_status::WorkingStatus,
     Result,
 };
 
+/// An authenticated client of Jobcan.
 pub struct Jobcan {
     account: Account,
     http_client: reqwest::Client,
+    session: Arc<Session>,
+    session_file: Option<PathBuf>,
+    retry: RetryPolicy,
+    login_url: Url,
+    employee_url: Url,
+    stamp_url: Url,
+    attendance_url: Url,
+    correction_url: Url,
+    correction_insert_url: Url,
+    leave_url: Url,
+    leave_form_url: Url,
+    leave_create_url: Url,
+    overtime_url: Url,
+    overtime_form_url: Url,
+    overtime_create_url: Url,
+    application_url: Url,
+    shift_url: Url,
 }
 
 impl Jobcan {
-    const LOGIN_URL: &'static str = "https://id.jobcan.jp/users/sign_in";
-    const EMPLOYEE_URL: &'static str = "https://ssl.jobcan.jp/employee";
-    const STAMP_URL: &'static str = "https://ssl.jobcan.jp/employee/index/adit";
+    pub const DEFAULT_ID_URL: &'static str = "https://id.jobcan.jp";
+    pub const DEFAULT_SSL_URL: &'static str = "https://ssl.jobcan.jp";
+
+    pub const DEFAULT_USER_AGENT: &'static str =
+        concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+    /// Create a client of the production Jobcan without session persistence.
+    ///
+    /// Fails if the HTTP client can't be built, e.g. TLS is unavailable.
+    pub fn new(account: Account) -> Result<Jobcan> {
+        Self::builder(account).build()
+    }
 
-    pub fn new(account: Account) -> Jobcan {
-        Jobcan {
+    pub fn builder(account: Account) -> JobcanBuilder {
+        JobcanBuilder {
             account,
-            http_client: reqwest::Client::builder()
-                .cookie_store(true)
-                .build()
-                .unwrap(),
+            id_url: Self::DEFAULT_ID_URL.into(),
+            ssl_url: Self::DEFAULT_SSL_URL.into(),
+            session_file: None,
+            retry: RetryPolicy::default(),
+            connect_timeout: None,
+            timeout: None,
+            proxy: None,
+            proxy_credentials: None,
+            root_certificates: Vec::new(),
+            user_agent: Self::DEFAULT_USER_AGENT.into(),
         }
     }
 
-    pub async fn login(&self) -> Result<()> {
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    /// Reuse the current session if it is still valid, otherwise login again.
+    ///
+    /// Returns the employee page loaded on the way, so that it needn't be loaded again.
+    pub async fn ensure_login(&self) -> Result<EmployeePage> {
+        let res = self.fetch_employee_page().await?;
+        if res.url().path() == self.employee_url.path() {
+            self.save_session()?;
+            self.parse_employee_page(res).await
+        } else {
+            self.login().await
+        }
+    }
+
+    /// Sign in with the account, failing with [`JobcanError::AuthError`] on bad credentials.
+    ///
+    /// Returns the employee page Jobcan lands on after login.
+    pub async fn login(&self) -> Result<EmployeePage> {
         let res = self.fetch_login_page().await?;
-        let body = res.text().await.map_err(|e| JobcanError::ReqwestError {
-            message: "Failed to get contents in login page".into(),
-            url: Self::LOGIN_URL.into(),
-            raw_error: e,
+        let body = res.text().await.map_err(|e| {
+            JobcanError::reqwest("Failed to get contents in login page", &self.login_url, e)
         })?;
         let html = scraper::Html::parse_document(&body);
 
@@ -43,30 +109,40 @@ impl Jobcan {
         let params = [
             ("authenticity_token", token.as_str()),
             ("user[email]", self.account.email()),
-            ("user[password]", self.account.password()),
+            ("user[password]", self.account.password().expose_secret()),
             ("app_key", "atd"),
             ("commit", "Login"),
         ];
 
         let res = self
-            .http_client
-            .post(Self::LOGIN_URL)
-            .form(&params)
-            .send()
-            .await
-            .map_err(|e| JobcanError::ReqwestError {
-                message: "Failed to request login".into(),
-                url: Self::LOGIN_URL.into(),
-                raw_error: e,
-            })?;
+            .send(
+                || self.http_client.post(self.login_url.clone()).form(&params),
+                "Failed to request login",
+                &self.login_url,
+            )
+            .await?;
 
-        if res.url().path() == "/employee" {
-            Ok(())
+        if res.url().path() == self.employee_url.path() {
+            self.save_session()?;
+            self.parse_employee_page(res).await
         } else {
-            Err(JobcanError::AuthError.into())
+            Err(JobcanError::AuthError)
+        }
+    }
+
+    fn save_session(&self) -> Result<()> {
+        match &self.session_file {
+            Some(path) => self.session.save(path),
+            None => Ok(()),
         }
     }
 
+    /// Stamp `stamp_type` against `group_id`, and check that Jobcan moved to the expected status.
+    ///
+    /// Fails without stamping with [`JobcanError::GroupError`] if the account doesn't belong to
+    /// `group_id`, since Jobcan would record the stamp anyway, and with
+    /// [`JobcanError::StampTransitionError`] if the stamp is not allowed in the current working
+    /// status, e.g. [`Stamp::ClockIn`] while working.
     pub async fn stamp(
         &self,
         stamp_type: Stamp,
@@ -74,97 +150,460 @@ impl Jobcan {
         is_night_shift: bool,
         note: &str,
     ) -> Result<()> {
-        let res = self.fetch_employee_page().await?;
-        let body = res.text().await.map_err(|e| JobcanError::ReqwestError {
-            message: "Failed to get contents in employee page".into(),
-            url: Self::EMPLOYEE_URL.into(),
-            raw_error: e,
-        })?;
-        let html = scraper::Html::parse_document(&body);
+        let page = self.employee_page().await?;
+        self.stamp_on(&page, stamp_type, group_id, is_night_shift, note)
+            .await
+    }
+
+    /// Same as [`Jobcan::stamp`], but with the status and token of `page` fetched beforehand.
+    pub async fn stamp_on(
+        &self,
+        page: &EmployeePage,
+        stamp_type: Stamp,
+        group_id: &str,
+        is_night_shift: bool,
+        note: &str,
+    ) -> Result<()> {
+        // Note: Without the group selector, e.g. when the company has a single group, there is
+        // nothing to check against
+        if !page.groups().is_empty() {
+            Group::find_by_id(page.groups(), group_id)?;
+        }
 
-        let token = HtmlExtractor::token(&html)?;
+        let status = page.working_status();
+        if !stamp_type.is_allowed_in(&status) {
+            return Err(JobcanError::StampTransitionError {
+                stamp: stamp_type,
+                status,
+            });
+        }
+
+        let mut token = page.token().to_string();
         let is_yakin = if is_night_shift { "1" } else { "0" };
-        let params = [
-            ("is_yakin", is_yakin),
-            ("adit_item", &stamp_type.to_request_params()),
-            ("notice", note),
-            ("token", token.as_ref()),
-            ("adit_group_id", group_id),
-            ("_", ""),
-        ];
+        let mut retry = 0;
+        loop {
+            let params = [
+                ("is_yakin", is_yakin),
+                ("adit_item", &stamp_type.to_request_params()),
+                ("notice", note),
+                ("token", token.as_ref()),
+                ("adit_group_id", group_id),
+                ("_", ""),
+            ];
 
-        let res = self
-            .http_client
-            .post(Self::STAMP_URL)
-            .form(&params)
-            .send()
-            .await
-            .map_err(|e| JobcanError::ReqwestError {
-                message: format!("Failed to request {}", stamp_type),
-                url: Self::STAMP_URL.into(),
-                raw_error: e,
-            })?;
+            let result = self
+                .http_client
+                .post(self.stamp_url.clone())
+                .form(&params)
+                .send()
+                .await;
+            if !Self::is_transient(&result) || retry >= self.retry.max_retries {
+                let res = Self::server_error(result).map_err(|e| {
+                    JobcanError::reqwest(
+                        format!("Failed to request {}", stamp_type),
+                        &self.stamp_url,
+                        e,
+                    )
+                })?;
+                return self.handle_stamp_response(res, stamp_type).await;
+            }
+
+            retry += 1;
+            tokio::time::sleep(self.retry.backoff(retry)).await;
 
-        self.handle_stamp_response(res, stamp_type).await
+            // Note: The stamp may have landed even though the request failed, so check the
+            // status again instead of stamping twice
+            let page = self.employee_page().await?;
+            let status = page.working_status();
+            if status == stamp_type.status_after() {
+                return Ok(());
+            }
+            if !stamp_type.is_allowed_in(&status) {
+                return Err(JobcanError::StampTransitionError {
+                    stamp: stamp_type,
+                    status,
+                });
+            }
+            token = page.token().to_string();
+        }
     }
 
-    pub async fn work_status(&self) -> Result<WorkingStatus> {
+    /// Load and parse the employee page, which the status, groups and stamps are read from.
+    pub async fn employee_page(&self) -> Result<EmployeePage> {
         let res = self.fetch_employee_page().await?;
-        let body = res.text().await.map_err(|e| JobcanError::ReqwestError {
-            message: "Failed to get contents in employee page".into(),
-            url: Self::EMPLOYEE_URL.into(),
-            raw_error: e,
+        self.parse_employee_page(res).await
+    }
+
+    async fn parse_employee_page(&self, res: Response) -> Result<EmployeePage> {
+        let body = res.text().await.map_err(|e| {
+            JobcanError::reqwest(
+                "Failed to get contents in employee page",
+                &self.employee_url,
+                e,
+            )
         })?;
-        HtmlExtractor::working_status(&body)
+        HtmlExtractor::employee_page(&body)
+    }
+
+    /// Current working status of the account.
+    pub async fn work_status(&self) -> Result<WorkingStatus> {
+        Ok(self.employee_page().await?.working_status())
     }
 
+    /// Groups the account can stamp against.
     pub async fn list_groups(&self) -> Result<Vec<Group>> {
-        let res = self.fetch_employee_page().await?;
-        let body = res.text().await.map_err(|e| JobcanError::ReqwestError {
-            message: "Failed to get contents in employee page".into(),
-            url: Self::EMPLOYEE_URL.into(),
-            raw_error: e,
-        })?;
-        let html = scraper::Html::parse_document(&body);
-        HtmlExtractor::groups(&html)
+        Ok(self.employee_page().await?.groups)
     }
 
+    /// Group used by Jobcan when no group is chosen.
     pub async fn default_group_id(&self) -> Result<String> {
-        let res = self.fetch_employee_page().await?;
-        let body = res.text().await.map_err(|e| JobcanError::ReqwestError {
-            message: "Failed to get contents in employee page".into(),
-            url: Self::EMPLOYEE_URL.into(),
-            raw_error: e,
+        Ok(self.employee_page().await?.default_group_id)
+    }
+
+    /// Stamps made today, oldest first.
+    pub async fn today_stamps(&self) -> Result<Vec<StampLog>> {
+        Ok(self.employee_page().await?.stamp_logs)
+    }
+
+    /// Attendance record (出勤簿) of `year`/`month`, one entry per day.
+    pub async fn attendance(&self, year: i32, month: u32) -> Result<Vec<AttendanceRecord>> {
+        let query = [
+            ("list_type", "normal".to_string()),
+            ("search_type", "month".to_string()),
+            ("year", year.to_string()),
+            ("month", month.to_string()),
+        ];
+        let res = self
+            .send(
+                || {
+                    self.http_client
+                        .get(self.attendance_url.clone())
+                        .query(&query)
+                },
+                "Failed to request attendance page",
+                &self.attendance_url,
+            )
+            .await?;
+        let body = res.text().await.map_err(|e| {
+            JobcanError::reqwest(
+                "Failed to get contents in attendance page",
+                &self.attendance_url,
+                e,
+            )
         })?;
-        HtmlExtractor::default_group_id(&body)
+        let html = scraper::Html::parse_document(&body);
+        HtmlExtractor::attendance_records(&html, year, month)
     }
 
-    async fn fetch_login_page(&self) -> Result<Response> {
-        self.http_client
-            .get(Self::LOGIN_URL)
-            .send()
-            .await
-            .map_err(|e| JobcanError::ReqwestError {
-                message: "Failed to request login page".into(),
-                url: Self::LOGIN_URL.into(),
-                raw_error: e,
+    /// Submit a stamp correction request (打刻修正申請) and return it as listed on the
+    /// correction page afterwards, with the state of its approval.
+    ///
+    /// Fails without submitting with [`JobcanError::GroupError`] if the account doesn't belong
+    /// to the group of `request`.
+    pub async fn request_correction(&self, request: &CorrectionRequest) -> Result<Correction> {
+        let date = request.date;
+        let query = [
+            ("year", date.year().to_string()),
+            ("month", date.month().to_string()),
+            ("day", date.day().to_string()),
+        ];
+        let body = self
+            .fetch_page(&self.correction_url, &query, "correction page")
+            .await?;
+        let (token, groups) = {
+            let html = scraper::Html::parse_document(&body);
+            (
+                HtmlExtractor::token(&html)?,
+                HtmlExtractor::correction_groups(&html)?,
+            )
+        };
+        if !groups.is_empty() {
+            Group::find_by_id(&groups, &request.group_id)?;
+        }
+
+        let time = request.time.to_request_params();
+        let params = [
+            ("token", token.as_str()),
+            ("year", &query[0].1),
+            ("month", &query[1].1),
+            ("day", &query[2].1),
+            ("time", &time),
+            ("group_id", &request.group_id),
+            ("adit_item", &request.stamp.to_request_params()),
+            ("notice", &request.reason),
+        ];
+        // Note: Jobcan redirects to the correction page, which lists the new request
+        let body = self
+            .submit_form(&self.correction_insert_url, &params, "Correction")
+            .await?;
+        let html = scraper::Html::parse_document(&body);
+        HtmlExtractor::corrections(&html, date)?
+            .into_iter()
+            .rev()
+            .find(|correction| {
+                correction.time.parse::<ClockTime>() == Ok(request.time)
+                    && correction.stamp == request.stamp
+            })
+            .ok_or_else(|| JobcanError::UnexpectedResponseError {
+                message: format!(
+                    "Correction of {} at {} on {} is not found after the request",
+                    request.stamp, request.time, date
+                ),
             })
     }
 
-    async fn fetch_employee_page(&self) -> Result<Response> {
-        self.http_client
-            .get(Self::EMPLOYEE_URL)
+    /// Remaining days of each leave type, e.g. paid leave (有給休暇).
+    pub async fn leave_balances(&self) -> Result<Vec<LeaveBalance>> {
+        let body = self.fetch_page(&self.leave_url, &[], "leave page").await?;
+        let html = scraper::Html::parse_document(&body);
+        HtmlExtractor::leave_balances(&html)
+    }
+
+    /// Submit a leave application (休暇申請) and return it as listed on the leave page
+    /// afterwards, with the state of its approval.
+    ///
+    /// Fails without submitting with [`JobcanError::LeaveTypeError`] if no leave type or more
+    /// than one leave type matches the name of `request`.
+    pub async fn request_leave(&self, request: &LeaveRequest) -> Result<LeaveApplication> {
+        let body = self
+            .fetch_page(&self.leave_form_url, &[], "leave application form")
+            .await?;
+        let (token, leave_type) = {
+            let html = scraper::Html::parse_document(&body);
+            let leave_types = HtmlExtractor::leave_types(&html)?;
+            (
+                HtmlExtractor::token(&html)?,
+                LeaveType::find_by_name(&leave_types, &request.leave_type)?.clone(),
+            )
+        };
+
+        let date = request.date;
+        let (year, month, day) = (
+            date.year().to_string(),
+            date.month().to_string(),
+            date.day().to_string(),
+        );
+        let params = [
+            ("token", token.as_str()),
+            ("year", &year),
+            ("month", &month),
+            ("day", &day),
+            ("holiday_type_id", leave_type.id()),
+            ("half_day", request.period.to_request_params()),
+            ("notice", &request.reason),
+        ];
+        // Note: Jobcan redirects to the leave page, which lists the new application
+        let body = self
+            .submit_form(&self.leave_create_url, &params, "Leave application")
+            .await?;
+        let html = scraper::Html::parse_document(&body);
+        HtmlExtractor::leave_applications(&html)?
+            .into_iter()
+            .rev()
+            .find(|application| {
+                application.date == date
+                    && application.leave_type == leave_type.name()
+                    && application.period == request.period
+            })
+            .ok_or_else(|| JobcanError::UnexpectedResponseError {
+                message: format!(
+                    "Leave application of {} on {} is not found after the request",
+                    leave_type.name(),
+                    date
+                ),
+            })
+    }
+
+    /// Overtime applications (残業申請) of `year`/`month`.
+    pub async fn overtime_applications(
+        &self,
+        year: i32,
+        month: u32,
+    ) -> Result<Vec<OvertimeApplication>> {
+        let query = [("year", year.to_string()), ("month", month.to_string())];
+        let body = self
+            .fetch_page(&self.overtime_url, &query, "overtime page")
+            .await?;
+        let html = scraper::Html::parse_document(&body);
+        HtmlExtractor::overtime_applications(&html)
+    }
+
+    /// Submit an overtime application (残業申請) and return it as listed on the overtime page
+    /// afterwards, with the state of its approval.
+    pub async fn request_overtime(&self, request: &OvertimeRequest) -> Result<OvertimeApplication> {
+        let body = self
+            .fetch_page(&self.overtime_form_url, &[], "overtime application form")
+            .await?;
+        let token = HtmlExtractor::token(&scraper::Html::parse_document(&body))?;
+
+        let date = request.date;
+        let (year, month, day) = (
+            date.year().to_string(),
+            date.month().to_string(),
+            date.day().to_string(),
+        );
+        let time = request.end_time.to_request_params();
+        let params = [
+            ("token", token.as_str()),
+            ("year", &year),
+            ("month", &month),
+            ("day", &day),
+            ("end_time", &time),
+            ("notice", &request.reason),
+        ];
+        // Note: Jobcan redirects to the overtime page of the month, which lists the new
+        // application
+        let body = self
+            .submit_form(&self.overtime_create_url, &params, "Overtime application")
+            .await?;
+        let html = scraper::Html::parse_document(&body);
+        // Note: Compared as times, since Jobcan may list `9:00` for `09:00`
+        HtmlExtractor::overtime_applications(&html)?
+            .into_iter()
+            .rev()
+            .find(|application| {
+                application.date == date
+                    && application.end_time.parse::<ClockTime>() == Ok(request.end_time)
+            })
+            .ok_or_else(|| JobcanError::UnexpectedResponseError {
+                message: format!(
+                    "Overtime application until {} on {} is not found after the request",
+                    request.end_time, date
+                ),
+            })
+    }
+
+    /// Application history (申請一覧) of corrections, leave, overtime and other applications,
+    /// with the state of each.
+    pub async fn applications(&self) -> Result<Vec<Application>> {
+        let body = self
+            .fetch_page(&self.application_url, &[], "application page")
+            .await?;
+        let html = scraper::Html::parse_document(&body);
+        HtmlExtractor::applications(&html)
+    }
+
+    /// Planned shifts (シフト) of each day of `year`/`month`.
+    pub async fn shifts(&self, year: i32, month: u32) -> Result<Vec<Shift>> {
+        let query = [("year", year.to_string()), ("month", month.to_string())];
+        let body = self
+            .fetch_page(&self.shift_url, &query, "shift page")
+            .await?;
+        let html = scraper::Html::parse_document(&body);
+        HtmlExtractor::shifts(&html)
+    }
+
+    /// Load the page at `url` as text, retrying transient failures.
+    async fn fetch_page(&self, url: &Url, query: &[(&str, String)], page: &str) -> Result<String> {
+        let res = self
+            .send(
+                || self.http_client.get(url.clone()).query(query),
+                &format!("Failed to request {}", page),
+                url,
+            )
+            .await?;
+        res.text().await.map_err(|e| {
+            JobcanError::reqwest(format!("Failed to get contents in {}", page), url, e)
+        })
+    }
+
+    /// Post an application form to `url` and return the page Jobcan answers with. Fails with
+    /// [`JobcanError::UnexpectedResponseError`] if the page shows an error of the form.
+    async fn submit_form(&self, url: &Url, params: &[(&str, &str)], form: &str) -> Result<String> {
+        // Note: Not retried, since a request which landed would be submitted twice
+        let res = self
+            .http_client
+            .post(url.clone())
+            .form(params)
             .send()
             .await
-            .map_err(|e| JobcanError::ReqwestError {
-                message: "Failed to request employee page".into(),
-                url: Self::EMPLOYEE_URL.into(),
-                raw_error: e,
-            })
+            .map_err(|e| JobcanError::reqwest(format!("Failed to request {}", form), url, e))?;
+        if !res.status().is_success() {
+            return Err(JobcanError::UnexpectedResponseError {
+                message: format!("Unexpected status found: {}", res.status()),
+            });
+        }
+        let body = res
+            .text()
+            .await
+            .map_err(|e| JobcanError::reqwest("Failed to get contents in response", url, e))?;
+
+        let html = scraper::Html::parse_document(&body);
+        match HtmlExtractor::alert(&html) {
+            Some(alert) => Err(JobcanError::UnexpectedResponseError {
+                message: format!("{} was rejected by Jobcan: {}", form, alert),
+            }),
+            None => Ok(body),
+        }
+    }
+
+    async fn fetch_login_page(&self) -> Result<Response> {
+        self.send(
+            || self.http_client.get(self.login_url.clone()),
+            "Failed to request login page",
+            &self.login_url,
+        )
+        .await
+    }
+
+    async fn fetch_employee_page(&self) -> Result<Response> {
+        self.send(
+            || self.http_client.get(self.employee_url.clone()),
+            "Failed to request employee page",
+            &self.employee_url,
+        )
+        .await
+    }
+
+    /// Send the request built by `request`, retrying transient failures with backoff.
+    /// Only for requests which are safe to repeat.
+    async fn send(
+        &self,
+        request: impl Fn() -> RequestBuilder,
+        message: &str,
+        url: &Url,
+    ) -> Result<Response> {
+        let mut retry = 0;
+        loop {
+            let result = request().send().await;
+            if !Self::is_transient(&result) || retry >= self.retry.max_retries {
+                return Self::server_error(result)
+                    .map_err(|e| JobcanError::reqwest(message, url, e));
+            }
+
+            retry += 1;
+            tokio::time::sleep(self.retry.backoff(retry)).await;
+        }
+    }
+
+    /// Turn a 5xx response left after retries into an error.
+    fn server_error(result: reqwest::Result<Response>) -> reqwest::Result<Response> {
+        match result {
+            Ok(res) if RetryPolicy::is_transient_status(res.status()) => res.error_for_status(),
+            result => result,
+        }
+    }
+
+    fn is_transient(result: &reqwest::Result<Response>) -> bool {
+        match result {
+            Ok(res) => RetryPolicy::is_transient_status(res.status()),
+            Err(e) => RetryPolicy::is_transient_error(e),
+        }
     }
 
     async fn handle_stamp_response(&self, res: Response, stamp_type: Stamp) -> Result<()> {
-        let content_type = res.headers().get("content-type").expect("No content-type");
+        if !res.status().is_success() {
+            return Err(JobcanError::UnexpectedResponseError {
+                message: format!("Unexpected status found: {}", res.status()),
+            });
+        }
+
+        let Some(content_type) = res.headers().get("content-type") else {
+            return Err(JobcanError::UnexpectedResponseError {
+                message: "Unexpected content-type found: expected application/json, got none"
+                    .into(),
+            });
+        };
         if content_type != "application/json" {
             return Err(JobcanError::UnexpectedResponseError {
                 message: format!(
@@ -177,11 +616,7 @@ impl Jobcan {
         let json = res
             .json::<stamp::Response>()
             .await
-            .map_err(|e| JobcanError::ReqwestError {
-                message: "Failed to parse response".into(),
-                url: Self::STAMP_URL.into(),
-                raw_error: e,
-            })?;
+            .map_err(|e| JobcanError::reqwest("Failed to parse response", &self.stamp_url, e))?;
 
         if json == stamp_type.expected_response() {
             Ok(())
@@ -196,3 +631,210 @@ impl Jobcan {
         }
     }
 }
+
+/// Builder of [`Jobcan`], created by [`Jobcan::builder`].
+pub struct JobcanBuilder {
+    account: Account,
+    id_url: String,
+    ssl_url: String,
+    session_file: Option<PathBuf>,
+    retry: RetryPolicy,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    proxy: Option<String>,
+    proxy_credentials: Option<(String, SecretString)>,
+    root_certificates: Vec<Vec<u8>>,
+    user_agent: String,
+}
+
+impl JobcanBuilder {
+    /// Base URL of the sign-in service. Default to [`Jobcan::DEFAULT_ID_URL`].
+    pub fn id_url(mut self, url: impl Into<String>) -> JobcanBuilder {
+        self.id_url = url.into();
+        self
+    }
+
+    /// Base URL of the attendance service. Default to [`Jobcan::DEFAULT_SSL_URL`].
+    pub fn ssl_url(mut self, url: impl Into<String>) -> JobcanBuilder {
+        self.ssl_url = url.into();
+        self
+    }
+
+    /// Restore cookies from `path` and write them back after each login, so that the session
+    /// can be reused by later invocations.
+    pub fn session_file(mut self, path: PathBuf) -> JobcanBuilder {
+        self.session_file = Some(path);
+        self
+    }
+
+    /// How transient failures are retried. Default to [`RetryPolicy::default`].
+    pub fn retry(mut self, policy: RetryPolicy) -> JobcanBuilder {
+        self.retry = policy;
+        self
+    }
+
+    /// Timeout of establishing a connection. No timeout by default.
+    pub fn connect_timeout(mut self, timeout: Duration) -> JobcanBuilder {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout of each request, from connecting until the whole response is read. No timeout
+    /// by default.
+    pub fn timeout(mut self, timeout: Duration) -> JobcanBuilder {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Send all requests through the HTTP(S) proxy at `url`, e.g. `http://proxy.example.com:8080`.
+    /// By default, the proxy of the `HTTPS_PROXY`/`HTTP_PROXY` environment variables is used.
+    pub fn proxy(mut self, url: impl Into<String>) -> JobcanBuilder {
+        self.proxy = Some(url.into());
+        self
+    }
+
+    /// Basic authentication of the proxy given by [`JobcanBuilder::proxy`].
+    pub fn proxy_credentials(
+        mut self,
+        username: impl Into<String>,
+        password: impl Into<SecretString>,
+    ) -> JobcanBuilder {
+        self.proxy_credentials = Some((username.into(), password.into()));
+        self
+    }
+
+    /// Trust a PEM encoded root certificate in addition to the system ones, e.g. a private CA
+    /// of a corporate proxy.
+    pub fn add_root_certificate(mut self, pem: Vec<u8>) -> JobcanBuilder {
+        self.root_certificates.push(pem);
+        self
+    }
+
+    /// `User-Agent` header of requests. Default to [`Jobcan::DEFAULT_USER_AGENT`].
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> JobcanBuilder {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    pub fn build(self) -> Result<Jobcan> {
+        let login_url = Self::join(&self.id_url, "users/sign_in")?;
+        let employee_url = Self::join(&self.ssl_url, "employee")?;
+        let stamp_url = Self::join(&self.ssl_url, "employee/index/adit")?;
+        let attendance_url = Self::join(&self.ssl_url, "employee/attendance")?;
+        let correction_url = Self::join(&self.ssl_url, "employee/adit/modify/")?;
+        let correction_insert_url = Self::join(&self.ssl_url, "employee/adit/insert/")?;
+        let leave_url = Self::join(&self.ssl_url, "employee/holiday/")?;
+        let leave_form_url = Self::join(&self.ssl_url, "employee/holiday/new")?;
+        let leave_create_url = Self::join(&self.ssl_url, "employee/holiday/create")?;
+        let overtime_url = Self::join(&self.ssl_url, "employee/over-work/")?;
+        let overtime_form_url = Self::join(&self.ssl_url, "employee/over-work/new")?;
+        let overtime_create_url = Self::join(&self.ssl_url, "employee/over-work/create")?;
+        let application_url = Self::join(&self.ssl_url, "employee/request/")?;
+        let shift_url = Self::join(&self.ssl_url, "employee/shift/")?;
+
+        let session = match &self.session_file {
+            Some(path) => Session::load(path)?,
+            None => Session::new(),
+        };
+        let session = Arc::new(session);
+        let http_client = self.http_client(Arc::clone(&session))?;
+
+        Ok(Jobcan {
+            account: self.account,
+            http_client,
+            session,
+            session_file: self.session_file,
+            retry: self.retry,
+            login_url,
+            employee_url,
+            stamp_url,
+            attendance_url,
+            correction_url,
+            correction_insert_url,
+            leave_url,
+            leave_form_url,
+            leave_create_url,
+            overtime_url,
+            overtime_form_url,
+            overtime_create_url,
+            application_url,
+            shift_url,
+        })
+    }
+
+    fn http_client(&self, session: Arc<Session>) -> Result<reqwest::Client> {
+        let error = |message: &str| {
+            let message = message.to_string();
+            move |raw_error| JobcanError::ClientBuildError { message, raw_error }
+        };
+
+        let mut builder = reqwest::Client::builder()
+            .cookie_provider(session)
+            .user_agent(&self.user_agent);
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(url) = &self.proxy {
+            let mut proxy = reqwest::Proxy::all(url).map_err(error("Invalid proxy URL"))?;
+            if let Some((username, password)) = &self.proxy_credentials {
+                proxy = proxy.basic_auth(username, password.expose_secret());
+            }
+            builder = builder.proxy(proxy);
+        }
+        for pem in &self.root_certificates {
+            let certificate =
+                reqwest::Certificate::from_pem(pem).map_err(error("Invalid root certificate"))?;
+            builder = builder.add_root_certificate(certificate);
+        }
+
+        builder
+            .build()
+            .map_err(error("Failed to build the HTTP client"))
+    }
+
+    fn join(base: &str, path: &str) -> Result<Url> {
+        // Note: Without a trailing slash, `Url::join` would replace the last path segment
+        let base = format!("{}/", base.trim_end_matches('/'));
+        Url::parse(&base)
+            .and_then(|url| url.join(path))
+            .map_err(|e| JobcanError::UrlParseError {
+                url: base,
+                raw_error: e,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn join_url_without_trailing_slash() {
+        // Act
+        let url = JobcanBuilder::join("http://localhost:8080/mock", "employee/index/adit");
+
+        // Assert
+        assert!(url.unwrap().as_str() == "http://localhost:8080/mock/employee/index/adit");
+    }
+
+    #[test]
+    fn join_url_with_trailing_slash() {
+        // Act
+        let url = JobcanBuilder::join("https://ssl.jobcan.jp/", "employee");
+
+        // Assert
+        assert!(url.unwrap().as_str() == "https://ssl.jobcan.jp/employee");
+    }
+
+    #[test]
+    fn join_invalid_url() {
+        // Act
+        let url = JobcanBuilder::join("ssl.jobcan.jp", "employee");
+
+        // Assert
+        assert!(url.is_err());
+    }
+}
@@ -0,0 +1,204 @@
+use std::fmt::Display;
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{
+    approval_state::ApprovalState, error::JobcanError, html_extractor::match_name, Result,
+};
+
+/// Remaining days of a leave type (休暇種別), as listed on the leave page.
+///
+/// Days are kept as Jobcan shows them (e.g. `10日`, `2.5日`), since some leave types are
+/// counted in hours as well.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LeaveBalance {
+    pub(crate) leave_type: String,
+    pub(crate) granted: Option<String>,
+    pub(crate) used: Option<String>,
+    pub(crate) remaining: Option<String>,
+    pub(crate) expires: Option<String>,
+}
+
+impl LeaveBalance {
+    /// Name of the leave type, e.g. `有給休暇`.
+    pub fn leave_type(&self) -> &str {
+        &self.leave_type
+    }
+
+    pub fn granted(&self) -> Option<&str> {
+        self.granted.as_deref()
+    }
+
+    pub fn used(&self) -> Option<&str> {
+        self.used.as_deref()
+    }
+
+    pub fn remaining(&self) -> Option<&str> {
+        self.remaining.as_deref()
+    }
+
+    /// Expiry of the remaining days (有効期限).
+    pub fn expires(&self) -> Option<&str> {
+        self.expires.as_deref()
+    }
+}
+
+/// A leave type the employee can apply for.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LeaveType {
+    pub(crate) id: String,
+    pub(crate) name: String,
+}
+
+impl LeaveType {
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Find the leave type named `name` in `leave_types`, matched the same as
+    /// [`crate::Group::find_by_name`].
+    ///
+    /// Fails with [`JobcanError::LeaveTypeError`] if no type or more than one type matches.
+    pub fn find_by_name<'a>(leave_types: &'a [LeaveType], name: &str) -> Result<&'a LeaveType> {
+        match_name(leave_types, name, LeaveType::name).map_err(|candidates| match candidates {
+            Some(candidates) => JobcanError::LeaveTypeError {
+                message: format!("Leave type `{}` is ambiguous", name),
+                leave_types: candidates.into_iter().cloned().collect(),
+            },
+            None => JobcanError::LeaveTypeError {
+                message: format!("Leave type `{}` is not found", name),
+                leave_types: leave_types.to_vec(),
+            },
+        })
+    }
+}
+
+/// Part of the day a leave takes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeavePeriod {
+    #[default]
+    FullDay,
+    Morning,
+    Afternoon,
+}
+
+impl Display for LeavePeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            LeavePeriod::FullDay => "Full day",
+            LeavePeriod::Morning => "Morning",
+            LeavePeriod::Afternoon => "Afternoon",
+        };
+        f.pad(s)
+    }
+}
+
+impl LeavePeriod {
+    /// Parse the label Jobcan shows for a period, e.g. `午前休`.
+    pub fn from_label(label: Option<&str>) -> Option<LeavePeriod> {
+        match label {
+            None | Some("全日" | "終日") => Some(LeavePeriod::FullDay),
+            Some("午前" | "午前休") => Some(LeavePeriod::Morning),
+            Some("午後" | "午後休") => Some(LeavePeriod::Afternoon),
+            Some(_) => None,
+        }
+    }
+
+    pub fn to_request_params(&self) -> &'static str {
+        match self {
+            LeavePeriod::FullDay => "0",
+            LeavePeriod::Morning => "1",
+            LeavePeriod::Afternoon => "2",
+        }
+    }
+}
+
+/// A leave application (休暇申請) to submit with [`crate::Jobcan::request_leave`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaveRequest {
+    pub date: NaiveDate,
+    /// Name of the leave type, matched by [`LeaveType::find_by_name`].
+    pub leave_type: String,
+    pub period: LeavePeriod,
+    pub reason: String,
+}
+
+/// A leave application listed on the leave page, with its state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LeaveApplication {
+    pub(crate) date: NaiveDate,
+    pub(crate) leave_type: String,
+    pub(crate) period: LeavePeriod,
+    pub(crate) reason: Option<String>,
+    pub(crate) state: ApprovalState,
+}
+
+impl LeaveApplication {
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn leave_type(&self) -> &str {
+        &self.leave_type
+    }
+
+    pub fn period(&self) -> LeavePeriod {
+        self.period
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub fn state(&self) -> ApprovalState {
+        self.state
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leave_types() -> Vec<LeaveType> {
+        [("1", "有給休暇"), ("2", "特別休暇"), ("3", "Summer leave")]
+            .into_iter()
+            .map(|(id, name)| LeaveType {
+                id: id.into(),
+                name: name.into(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn find_leave_type_by_prefix() {
+        // Arrange
+        let leave_types = leave_types();
+
+        // Act
+        let leave_type = LeaveType::find_by_name(&leave_types, "summer");
+
+        // Assert
+        assert!(leave_type.unwrap().id() == "3");
+    }
+
+    #[test]
+    fn find_leave_type_by_unknown_name() {
+        // Arrange
+        let leave_types = leave_types();
+
+        // Act
+        let leave_type = LeaveType::find_by_name(&leave_types, "病気休暇");
+
+        // Assert
+        assert!(
+            leave_type.unwrap_err().to_string()
+                == "Leave type `病気休暇` is not found. Available leave types: 有給休暇, 特別休暇, Summer leave"
+        );
+    }
+}
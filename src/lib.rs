@@ -0,0 +1,57 @@
+//! A client library for [Jobcan](https://jobcan.ne.jp/), the attendance management service.
+//!
+//! ```no_run
+//! use jobcan::{Account, Jobcan, Stamp};
+//!
+//! # async fn run() -> jobcan::Result<()> {
+//! let jobcan = Jobcan::new(Account::new("user@example.com".into(), "password".to_string()))?;
+//!
+//! // Note: The employee page loaded by login serves both the default group and the stamp
+//! let page = jobcan.login().await?;
+//! jobcan
+//!     .stamp_on(&page, Stamp::ClockIn, page.default_group_id(), false, "")
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod account;
+pub mod application;
+pub mod approval_state;
+pub mod attendance;
+pub mod clock_time;
+pub mod correction;
+pub mod employee_page;
+pub mod error;
+pub mod html_extractor;
+pub mod jobcan;
+pub mod leave;
+pub mod overtime;
+pub mod retry;
+pub mod session;
+pub mod shift;
+pub mod stamp;
+pub mod stamp_log;
+pub mod working_status;
+
+pub use account::Account;
+pub use application::{Application, ApplicationKind};
+pub use approval_state::ApprovalState;
+pub use attendance::AttendanceRecord;
+pub use clock_time::ClockTime;
+pub use correction::{Correction, CorrectionRequest};
+pub use employee_page::EmployeePage;
+pub use error::JobcanError;
+pub use html_extractor::Group;
+pub use jobcan::{Jobcan, JobcanBuilder};
+pub use leave::{LeaveApplication, LeaveBalance, LeavePeriod, LeaveRequest, LeaveType};
+pub use overtime::{OvertimeApplication, OvertimeRequest};
+pub use retry::RetryPolicy;
+pub use shift::Shift;
+pub use stamp::Stamp;
+pub use stamp_log::{Elapsed, StampLog};
+pub use working_status::WorkingStatus;
+
+pub use secrecy::{ExposeSecret, SecretString};
+
+pub type Result<T> = std::result::Result<T, JobcanError>;
@@ -1,32 +1,85 @@
-mod account;
+mod calendar;
 mod cli;
-mod error;
-mod html_extractor;
-mod jobcan;
-mod stamp;
-mod working_status;
+mod config;
+mod credential_store;
+mod failure;
+mod japanese_holidays;
+mod output;
+mod password;
+mod schedule;
 
-use std::process::exit;
+use std::{env, fs, io, path::PathBuf, process::exit, sync::OnceLock, time::Duration};
 
-use account::Account;
+use calendar::{Calendar, CalendarConfig};
+use chrono::{Datelike, Local, NaiveDateTime};
 use clap::Parser;
-use error::JobcanError;
-use jobcan::Jobcan;
-use stamp::Stamp;
+use config::Config;
+use credential_store::CredentialStore;
+use failure::{ExitCode, Failure};
+use jobcan::{
+    Account, ApplicationKind, ApprovalState, CorrectionRequest, EmployeePage, Group, Jobcan,
+    JobcanBuilder, JobcanError, LeavePeriod, LeaveRequest, OvertimeRequest, RetryPolicy,
+    SecretString, Shift, Stamp, StampLog, WorkingStatus,
+};
+use output::OutputFormat;
+use schedule::{Event, Schedule};
+use serde::Serialize;
 
-pub type Result<T> = std::result::Result<T, JobcanError>;
+/// Environment variable holding the password of `--proxy-user`.
+const PROXY_PASSWORD_ENV: &str = "JOBCAN_PROXY_PASSWORD";
 
 fn success_exit() -> ! {
     exit(0);
 }
 
-fn error_exit() -> ! {
-    exit(1);
+/// Output format of the running command, which errors are printed in as well.
+static OUTPUT: OnceLock<OutputFormat> = OnceLock::new();
+
+fn error_exit(failure: impl Into<Failure>) -> ! {
+    let failure = failure.into();
+    match OUTPUT.get() {
+        Some(OutputFormat::Json) => {
+            let json = serde_json::json!({ "error": failure });
+            eprintln!("{}", json);
+        }
+        _ => eprintln!("{}", failure.message()),
+    }
+    exit(failure.exit_code());
+}
+
+/// Settings shared by all commands, resolved from flags, environment variables and the config file.
+struct Context {
+    endpoints: cli::Endpoints,
+    http: cli::Http,
+    output: OutputFormat,
+    profile: config::Profile,
+    credential_store: CredentialStore,
+    retry: RetryPolicy,
+    schedule: Schedule,
+    calendar: CalendarConfig,
 }
 
 #[tokio::main]
 async fn main() {
     let cli = cli::Cli::parse();
+    let (config, profile) = Config::load(cli.config.as_deref(), cli.profile.as_deref())
+        .unwrap_or_else(|e| {
+            // The profile is unknown yet, so errors of the config file follow the flag only
+            OUTPUT.set(cli.output.unwrap_or_default()).unwrap();
+            error_exit(Failure::new(ExitCode::ConfigError, e))
+        });
+    let output = cli.output.or(profile.output).unwrap_or_default();
+    OUTPUT.set(output).unwrap();
+    let context = Context {
+        endpoints: endpoints_from_cli(cli.endpoints, config.endpoints),
+        http: http_from_cli(cli.http, config.http),
+        output,
+        profile,
+        credential_store: cli.credential_store,
+        retry: retry_from_cli(cli.retries, config.retry),
+        schedule: config.schedule,
+        calendar: config.calendar,
+    };
 
     match cli.sub_command {
         cli::SubCommand::ClockIn {
@@ -34,42 +87,142 @@ async fn main() {
             group_id,
             night_shift,
             note,
-            ..
+            guards,
         } => {
-            run_stamp(credentials, group_id, night_shift, note, Stamp::ClockIn).await;
+            run_stamp(
+                credentials,
+                &context,
+                group_id,
+                night_shift,
+                note,
+                guards,
+                Stamp::ClockIn,
+            )
+            .await;
         }
         cli::SubCommand::ClockOut {
             credentials,
             group_id,
             night_shift,
             note,
-            ..
+            guards,
         } => {
-            run_stamp(credentials, group_id, night_shift, note, Stamp::ClockOut).await;
+            run_stamp(
+                credentials,
+                &context,
+                group_id,
+                night_shift,
+                note,
+                guards,
+                Stamp::ClockOut,
+            )
+            .await;
         }
         cli::SubCommand::StartBreak {
             credentials,
             group_id,
             night_shift,
             note,
-            ..
+            guards,
         } => {
-            run_stamp(credentials, group_id, night_shift, note, Stamp::StartBreak).await;
+            run_stamp(
+                credentials,
+                &context,
+                group_id,
+                night_shift,
+                note,
+                guards,
+                Stamp::StartBreak,
+            )
+            .await;
         }
         cli::SubCommand::EndBreak {
             credentials,
             group_id,
             night_shift,
             note,
-            ..
+            guards,
         } => {
-            run_stamp(credentials, group_id, night_shift, note, Stamp::EndBreak).await;
+            run_stamp(
+                credentials,
+                &context,
+                group_id,
+                night_shift,
+                note,
+                guards,
+                Stamp::EndBreak,
+            )
+            .await;
         }
-        cli::SubCommand::Status(credentials) => {
-            run_status(credentials).await;
+        cli::SubCommand::Status {
+            credentials,
+            verbose,
+        } => {
+            run_status(credentials, &context, verbose).await;
         }
         cli::SubCommand::ListGroups(credentials) => {
-            run_list_groups(credentials).await;
+            run_list_groups(credentials, &context).await;
+        }
+        cli::SubCommand::Attendance { credentials, month } => {
+            run_attendance(credentials, &context, month.into()).await;
+        }
+        cli::SubCommand::Correction {
+            credentials,
+            correction,
+            group_id,
+        } => {
+            run_correction(credentials, &context, correction, group_id).await;
+        }
+        cli::SubCommand::Leave {
+            sub_command: cli::LeaveCommand::Balance(credentials),
+        } => {
+            run_leave_balance(credentials, &context).await;
+        }
+        cli::SubCommand::Leave {
+            sub_command: cli::LeaveCommand::Request { credentials, leave },
+        } => {
+            run_leave_request(credentials, &context, leave).await;
+        }
+        cli::SubCommand::Overtime {
+            sub_command:
+                cli::OvertimeCommand::Request {
+                    credentials,
+                    overtime,
+                },
+        } => {
+            run_overtime_request(credentials, &context, overtime).await;
+        }
+        cli::SubCommand::Overtime {
+            sub_command: cli::OvertimeCommand::List { credentials, month },
+        } => {
+            run_overtime_list(credentials, &context, month.into()).await;
+        }
+        cli::SubCommand::Requests {
+            sub_command:
+                cli::RequestsCommand::List {
+                    credentials,
+                    filter,
+                },
+        } => {
+            run_requests_list(credentials, &context, filter).await;
+        }
+        cli::SubCommand::Shift {
+            sub_command: cli::ShiftCommand::Show { credentials, month },
+        } => {
+            run_shift_show(credentials, &context, month.into()).await;
+        }
+        cli::SubCommand::Daemon(credentials) => {
+            run_daemon(credentials, &context).await;
+        }
+        cli::SubCommand::Auth {
+            sub_command: cli::AuthCommand::Login(credentials),
+        } => {
+            run_auth_login(credentials, &context).await;
+        }
+        cli::SubCommand::Auth {
+            sub_command: cli::AuthCommand::Logout(email),
+        } => {
+            run_auth_logout(email, &context);
         }
     };
 
@@ -78,101 +231,862 @@ async fn main() {
 
 async fn run_stamp(
     credentials: cli::Credentials,
+    context: &Context,
     group_id: cli::GroupID,
     night_shift: cli::NightShift,
     note: cli::Notes,
+    guards: cli::Guards,
     stamp_type: Stamp,
 ) {
-    let account = account_from_cli(credentials);
-    let jobcan = Jobcan::new(account);
+    if guards.skip_holidays {
+        let today = Local::now().date_naive();
+        if let Some(holiday) = calendar_from_context(context).holiday(today) {
+            eprintln!(
+                "Skipped {} since {} is a holiday ({}).",
+                stamp_type, today, holiday
+            );
+            return;
+        }
+    }
+
+    let jobcan = jobcan_from_cli(credentials, context);
+
+    let page = jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    let group_id = group_id_from_cli(group_id, context, &page);
+
+    let note: String = Option::from(note)
+        .or(context.profile.notes.clone())
+        .unwrap_or_default();
+
+    let night_shift: bool = Option::from(night_shift)
+        .or(context.profile.night_shift)
+        .unwrap_or_default();
+
+    match jobcan
+        .stamp_on(&page, stamp_type, &group_id, night_shift, &note)
+        .await
+    {
+        Ok(()) => {}
+        Err(JobcanError::StampTransitionError { stamp, status }) if guards.if_needed => {
+            eprintln!("Skipped {} since the status is {}.", stamp, status);
+        }
+        Err(e) => error_exit(e),
+    }
+}
+
+/// Resolve the group of a stamp from the flags, the environment variables and the profile, in
+/// this order.
+fn group_id_from_cli(group_id: cli::GroupID, context: &Context, page: &EmployeePage) -> String {
+    // Note: The environment variables are read here instead of by clap, so that a name in
+    // $JOBCAN_GROUP can't take precedence over --group-id
+    let (group, group_id) = if group_id.group.is_some() || group_id.group_id.is_some() {
+        (group_id.group, group_id.group_id)
+    } else {
+        (env_var("JOBCAN_GROUP"), env_var("JOBCAN_GROUP_ID"))
+    };
+    resolve_group_id(group, group_id, &context.profile, page).unwrap_or_else(|e| error_exit(e))
+}
+
+/// Value of the environment variable `name`, treating an empty value as unset the same as clap.
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+/// ID of the group named `group` or of `group_id`, or of the profile if neither is given. A
+/// group name takes precedence over a group ID from the same source.
+fn resolve_group_id(
+    group: Option<String>,
+    group_id: Option<String>,
+    profile: &config::Profile,
+    page: &EmployeePage,
+) -> Result<String, JobcanError> {
+    let (group, group_id) = if group.is_some() || group_id.is_some() {
+        (group, group_id)
+    } else {
+        (profile.group.clone(), profile.group_id.clone())
+    };
+
+    match (group, group_id) {
+        (Some(name), _) => {
+            Group::find_by_name(page.groups(), &name).map(|group| group.id().to_string())
+        }
+        (None, Some(group_id)) => Ok(group_id),
+        (None, None) => Ok(page.default_group_id().to_string()),
+    }
+}
+
+/// Stamps missed by more than this, e.g. while the machine was asleep, are skipped.
+const MISSED_AFTER: Duration = Duration::from_secs(10 * 60);
+
+/// The longest sleep of `daemon`, so that a change of the clock is noticed.
+const MAX_SLEEP: Duration = Duration::from_secs(60);
+
+async fn run_daemon(credentials: cli::Credentials, context: &Context) {
+    let schedule = &context.schedule;
+    if schedule.is_empty() {
+        error_exit(Failure::new(
+            ExitCode::ConfigError,
+            "No stamp is scheduled. Add [schedule.<WEEKDAY>] tables to the config file.",
+        ));
+    }
+
+    let calendar = if schedule.skip_holidays {
+        calendar_from_context(context)
+    } else {
+        Calendar::default()
+    };
 
-    jobcan.login().await.unwrap_or_else(|e| {
-        eprintln!("{}", e);
-        error_exit();
+    let jobcan = jobcan_from_cli(credentials, context);
+    log(format!("Started for {}.", jobcan.account().email()));
+
+    let mut last = Local::now().naive_local();
+    while let Some(event) = schedule.next_event(last, &calendar) {
+        log(format!("Next: {} at {}.", event.stamp, event.at));
+        sleep_until(event.at).await;
+
+        let late = (Local::now().naive_local() - event.at)
+            .to_std()
+            .unwrap_or_default();
+        if late > MISSED_AFTER {
+            log(format!(
+                "Skipped {} at {} since it is {} minutes late.",
+                event.stamp,
+                event.at,
+                late.as_secs() / 60
+            ));
+        } else {
+            match run_scheduled_stamp(&jobcan, context, &event).await {
+                Ok(()) => log(format!("Stamped {}.", event.stamp)),
+                Err(JobcanError::StampTransitionError { stamp, status }) => {
+                    log(format!("Skipped {} since the status is {}.", stamp, status));
+                }
+                Err(e) => log(format!("Failed to stamp {}: {}", event.stamp, e)),
+            }
+        }
+        last = event.at;
+    }
+
+    // Note: Only reachable when every date within the lookahead is skipped or a holiday
+    log("No stamp is scheduled anymore.");
+}
+
+/// Stamp `event` after checking the current status, so that a stamp made by hand is not
+/// repeated.
+async fn run_scheduled_stamp(
+    jobcan: &Jobcan,
+    context: &Context,
+    event: &Event<'_>,
+) -> Result<(), JobcanError> {
+    let day = event.day;
+    let profile = &context.profile;
+
+    let page = jobcan.ensure_login().await?;
+    let group_id = resolve_group_id(day.group.clone(), day.group_id.clone(), profile, &page)?;
+    let note = day
+        .notes
+        .clone()
+        .or(profile.notes.clone())
+        .unwrap_or_default();
+    let night_shift = day.night_shift.or(profile.night_shift).unwrap_or_default();
+
+    jobcan
+        .stamp_on(&page, event.stamp, &group_id, night_shift, &note)
+        .await
+}
+
+async fn sleep_until(at: NaiveDateTime) {
+    loop {
+        let Ok(remaining) = (at - Local::now().naive_local()).to_std() else {
+            return;
+        };
+        if remaining.is_zero() {
+            return;
+        }
+        tokio::time::sleep(remaining.min(MAX_SLEEP)).await;
+    }
+}
+
+fn calendar_from_context(context: &Context) -> Calendar {
+    Calendar::load(&context.calendar)
+        .unwrap_or_else(|e| error_exit(Failure::new(ExitCode::ConfigError, e)))
+}
+
+/// Print a line of the `daemon` log with the local time.
+fn log(message: impl std::fmt::Display) {
+    println!("[{}] {}", Local::now().format("%Y-%m-%d %H:%M:%S"), message);
+}
+
+/// Output of `status`. Elapsed time, stamps and the planned shift are only set with `--verbose`.
+#[derive(Serialize)]
+struct StatusOutput<'a> {
+    status: &'a WorkingStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    work_minutes: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    break_minutes: Option<i64>,
+    // Note: CSV and TSV can't hold a list, so stamps are only in JSON
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stamps: Option<&'a [StampLog]>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    shift: Option<&'a Shift>,
+}
+
+async fn run_status(credentials: cli::Credentials, context: &Context, verbose: bool) {
+    let jobcan = jobcan_from_cli(credentials, context);
+
+    let page = jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+    let status = page.working_status();
+
+    let logs = if verbose { page.stamp_logs() } else { &[] };
+    let elapsed = verbose.then(|| StampLog::elapsed(logs, Local::now().time()));
+    let shift = if verbose {
+        today_shift(&jobcan).await
+    } else {
+        None
+    };
+
+    let document = StatusOutput {
+        status: &status,
+        work_minutes: elapsed.map(|e| e.work.num_minutes()),
+        break_minutes: elapsed.map(|e| e.rest.num_minutes()),
+        stamps: verbose.then_some(logs),
+        shift: shift.as_ref(),
+    };
+    let row = StatusOutput {
+        stamps: None,
+        shift: None,
+        ..document
+    };
+
+    context.output.print(&document, &[row], || {
+        println!("{}", status);
+
+        let Some(elapsed) = elapsed else {
+            return;
+        };
+
+        match &shift {
+            Some(shift) => {
+                let line = format!(
+                    "Planned shift: {}-{}  {}",
+                    shift.start().unwrap_or_default(),
+                    shift.end().unwrap_or_default(),
+                    shift.group().unwrap_or_default()
+                );
+                println!("{}", line.trim_end());
+            }
+            None => println!("Planned shift: none"),
+        }
+
+        for log in logs {
+            let line = format!(
+                "{:<5}  {:<10}  {}  {}",
+                log.time(),
+                log.stamp(),
+                log.group().unwrap_or("-"),
+                log.note().unwrap_or_default()
+            );
+            println!("{}", line.trim_end());
+        }
+        println!(
+            "Work time: {}, Break time: {}",
+            format_duration(elapsed.work),
+            format_duration(elapsed.rest)
+        );
     });
+}
 
-    let group_id: String = match group_id.group_id {
-        Some(group_id) => group_id,
-        None => jobcan.default_group_id().await.unwrap_or_else(|e| {
-            eprintln!("{}", e);
-            error_exit();
-        }),
+/// Planned shift of today, if any.
+async fn today_shift(jobcan: &Jobcan) -> Option<Shift> {
+    let today = Local::now().date_naive();
+    let shifts = match jobcan.shifts(today.year(), today.month()).await {
+        Ok(shifts) => shifts,
+        // Note: The shift page has no shift table unless the company manages shifts on Jobcan
+        Err(JobcanError::ElementExtractError { .. }) => return None,
+        Err(e) => error_exit(e),
     };
+    shifts
+        .into_iter()
+        .find(|shift| shift.date() == today && shift.start().is_some())
+}
+
+fn format_duration(duration: chrono::Duration) -> String {
+    format!(
+        "{:02}:{:02}",
+        duration.num_hours(),
+        duration.num_minutes() % 60
+    )
+}
+
+async fn run_list_groups(credentials: cli::Credentials, context: &Context) {
+    let jobcan = jobcan_from_cli(credentials, context);
+
+    let page = jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+    let groups = page.groups();
+
+    context.output.print(&groups, groups, || {
+        for group in groups {
+            println!("GroupID:{}, GroupName:{}", group.id(), group.name());
+        }
+    });
+}
 
-    let note: String = note.into();
+async fn run_attendance(credentials: cli::Credentials, context: &Context, month: cli::YearMonth) {
+    let jobcan = jobcan_from_cli(credentials, context);
 
     jobcan
-        .stamp(stamp_type, &group_id, night_shift.into(), &note)
+        .ensure_login()
         .await
-        .unwrap_or_else(|e| {
-            eprintln!("{}", e);
-            error_exit();
-        });
+        .unwrap_or_else(|e| error_exit(e));
+
+    let records = jobcan
+        .attendance(month.year, month.month)
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    context.output.print(&records, &records, || {
+        println!("Date        ClockIn   ClockOut  Break     Worked    Overtime  Notes");
+        for record in &records {
+            let notes = [record.holiday_type(), record.notes()]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(", ");
+            let line = format!(
+                "{:<10}  {:<8}  {:<8}  {:<8}  {:<8}  {:<8}  {}",
+                record.date(),
+                record.clock_in().unwrap_or("-"),
+                record.clock_out().unwrap_or("-"),
+                record.break_time().unwrap_or("-"),
+                record.work_time().unwrap_or("-"),
+                record.overtime().unwrap_or("-"),
+                notes
+            );
+            println!("{}", line.trim_end());
+        }
+    });
+}
+
+async fn run_correction(
+    credentials: cli::Credentials,
+    context: &Context,
+    correction: cli::CorrectionStamp,
+    group_id: cli::GroupID,
+) {
+    let jobcan = jobcan_from_cli(credentials, context);
+
+    // Note: Groups are resolved on the employee page, the same as stamps
+    let page = jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+    let group_id = group_id_from_cli(group_id, context, &page);
+
+    let request = CorrectionRequest {
+        date: correction.date.unwrap_or_else(|| Local::now().date_naive()),
+        time: correction.time,
+        stamp: correction.stamp.into(),
+        group_id,
+        reason: correction.reason,
+    };
+    let correction = jobcan
+        .request_correction(&request)
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    context.output.print(&correction, &[&correction], || {
+        println!(
+            "Requested {} at {} on {}: {}",
+            correction.stamp(),
+            correction.time(),
+            correction.date(),
+            correction.state()
+        );
+    });
 }
 
-async fn run_status(credentials: cli::Credentials) {
-    let account = account_from_cli(credentials);
-    let jobcan = Jobcan::new(account);
+async fn run_leave_balance(credentials: cli::Credentials, context: &Context) {
+    let jobcan = jobcan_from_cli(credentials, context);
+
+    jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    let balances = jobcan
+        .leave_balances()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
 
-    jobcan.login().await.unwrap_or_else(|e| {
-        eprintln!("{}", e);
-        error_exit();
+    context.output.print(&balances, &balances, || {
+        for balance in &balances {
+            let details = [
+                balance.granted().map(|days| format!("granted {}", days)),
+                balance.used().map(|days| format!("used {}", days)),
+                balance.expires().map(|date| format!("expires {}", date)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+            let mut line = format!(
+                "{}: {} remaining",
+                balance.leave_type(),
+                balance.remaining().unwrap_or("-")
+            );
+            if !details.is_empty() {
+                line = format!("{} ({})", line, details.join(", "));
+            }
+            println!("{}", line);
+        }
     });
+}
+
+async fn run_leave_request(
+    credentials: cli::Credentials,
+    context: &Context,
+    leave: cli::LeaveForm,
+) {
+    let jobcan = jobcan_from_cli(credentials, context);
 
-    let status = jobcan.work_status().await.unwrap_or_else(|e| {
-        eprintln!("{}", e);
-        error_exit();
+    jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    let request = LeaveRequest {
+        date: leave.date,
+        leave_type: leave.leave_type,
+        period: leave.half_day.map_or(LeavePeriod::FullDay, Into::into),
+        reason: leave.reason.unwrap_or_default(),
+    };
+    let application = jobcan
+        .request_leave(&request)
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    context.output.print(&application, &[&application], || {
+        println!(
+            "Requested {} ({}) on {}: {}",
+            application.leave_type(),
+            application.period(),
+            application.date(),
+            application.state()
+        );
     });
+}
+
+async fn run_overtime_request(
+    credentials: cli::Credentials,
+    context: &Context,
+    overtime: cli::OvertimeForm,
+) {
+    let jobcan = jobcan_from_cli(credentials, context);
 
-    println!("{}", status);
+    jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    let request = OvertimeRequest {
+        date: overtime.date.unwrap_or_else(|| Local::now().date_naive()),
+        end_time: overtime.end_time,
+        reason: overtime.reason,
+    };
+    let application = jobcan
+        .request_overtime(&request)
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    context.output.print(&application, &[&application], || {
+        println!(
+            "Requested overtime until {} on {}: {}",
+            application.end_time(),
+            application.date(),
+            application.state()
+        );
+    });
 }
 
-async fn run_list_groups(credentials: cli::Credentials) {
-    let account = account_from_cli(credentials);
-    let jobcan = Jobcan::new(account);
+async fn run_overtime_list(
+    credentials: cli::Credentials,
+    context: &Context,
+    month: cli::YearMonth,
+) {
+    let jobcan = jobcan_from_cli(credentials, context);
+
+    jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    let applications = jobcan
+        .overtime_applications(month.year, month.month)
+        .await
+        .unwrap_or_else(|e| error_exit(e));
 
-    jobcan.login().await.unwrap_or_else(|e| {
-        eprintln!("{}", e);
-        error_exit();
+    context.output.print(&applications, &applications, || {
+        println!("Date        End    Overtime  State             Reason");
+        for application in &applications {
+            let line = format!(
+                "{:<10}  {:<5}  {:<8}  {:<16}  {}",
+                application.date(),
+                application.end_time(),
+                application.overtime().unwrap_or("-"),
+                application.state(),
+                application.reason().unwrap_or_default()
+            );
+            println!("{}", line.trim_end());
+        }
     });
+}
+
+async fn run_requests_list(
+    credentials: cli::Credentials,
+    context: &Context,
+    filter: cli::RequestFilter,
+) {
+    let jobcan = jobcan_from_cli(credentials, context);
 
-    let groups = jobcan.list_groups().await.unwrap_or_else(|e| {
-        eprintln!("{}", e);
-        error_exit();
+    jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    let mut applications = jobcan
+        .applications()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    let states: Vec<ApprovalState> = filter.state.into_iter().map(Into::into).collect();
+    applications.retain(|application| {
+        (states.is_empty() || states.contains(&application.state()))
+            && filter.from.is_none_or(|from| application.date() >= from)
+            && filter.to.is_none_or(|to| application.date() <= to)
     });
 
-    for group in groups {
-        println!("GroupID:{}, GroupName:{}", group.id(), group.name());
-    }
+    context.output.print(&applications, &applications, || {
+        println!("Date        Type        State             Submitted         Approver");
+        for application in &applications {
+            // Note: Applications other than the known kinds are shown as Jobcan labels them
+            let kind = match application.kind() {
+                ApplicationKind::Other => application.label().to_string(),
+                kind => kind.to_string(),
+            };
+            let submitted_at = application
+                .submitted_at()
+                .map(|at| at.format("%Y-%m-%d %H:%M").to_string());
+            let line = format!(
+                "{:<10}  {:<10}  {:<16}  {:<16}  {}",
+                application.date(),
+                kind,
+                application.state(),
+                submitted_at.as_deref().unwrap_or("-"),
+                application.approver().unwrap_or_default()
+            );
+            println!("{}", line.trim_end());
+        }
+    });
 }
 
-fn account_from_cli(credentials: cli::Credentials) -> Account {
-    match credentials {
-        cli::Credentials {
-            email: Some(email),
-            password: Some(password),
-        } => Account::new(email, password),
-        cli::Credentials {
-            email: Some(_),
-            password: None,
-        } => {
-            eprintln!("jobcan password is required.");
-            error_exit();
+async fn run_shift_show(credentials: cli::Credentials, context: &Context, month: cli::YearMonth) {
+    let jobcan = jobcan_from_cli(credentials, context);
+
+    jobcan
+        .ensure_login()
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    let shifts = jobcan
+        .shifts(month.year, month.month)
+        .await
+        .unwrap_or_else(|e| error_exit(e));
+
+    context.output.print(&shifts, &shifts, || {
+        println!("Date        Start  End    Group");
+        for shift in &shifts {
+            let line = format!(
+                "{:<10}  {:<5}  {:<5}  {}",
+                shift.date(),
+                shift.start().unwrap_or("-"),
+                shift.end().unwrap_or("-"),
+                shift.group().unwrap_or_default()
+            );
+            println!("{}", line.trim_end());
         }
-        cli::Credentials {
-            email: None,
-            password: Some(_),
-        } => {
-            eprintln!("jobcan email is required.");
-            error_exit();
+    });
+}
+
+async fn run_auth_login(credentials: cli::Credentials, context: &Context) {
+    let Some(email) = email_from_cli(credentials.email, context) else {
+        error_exit(Failure::new(
+            ExitCode::MissingCredentials,
+            "jobcan email is required.",
+        ))
+    };
+    // Note: The saved password is never used here, since this command replaces it
+    let password = password_from_cli(
+        credentials.password,
+        &credentials.password_source,
+        Some(&email),
+        None,
+    )
+    .unwrap_or_else(|| {
+        error_exit(Failure::new(
+            ExitCode::MissingCredentials,
+            "jobcan password is required.",
+        ))
+    });
+
+    // Note: Login even with a saved session so that a wrong password is never saved
+    let jobcan = jobcan_from_account(Account::new(email.clone(), password), context);
+    jobcan.login().await.unwrap_or_else(|e| error_exit(e));
+
+    context
+        .credential_store
+        .set(&email, jobcan.account().password())
+        .unwrap_or_else(|e| error_exit(Failure::new(ExitCode::CredentialStoreError, e)));
+    println!("Saved the password of {}.", email);
+}
+
+fn run_auth_logout(email: cli::Email, context: &Context) {
+    let Some(email) = email_from_cli(email.email, context) else {
+        error_exit(Failure::new(
+            ExitCode::MissingCredentials,
+            "jobcan email is required.",
+        ))
+    };
+
+    let deleted = context
+        .credential_store
+        .delete(&email)
+        .unwrap_or_else(|e| error_exit(Failure::new(ExitCode::CredentialStoreError, e)));
+
+    if let Some(path) = session_file(&email) {
+        match fs::remove_file(&path) {
+            Ok(()) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => error_exit(JobcanError::SessionError {
+                message: "Failed to remove session file".into(),
+                path,
+                raw_error: e,
+            }),
         }
-        cli::Credentials {
-            email: None,
-            password: None,
-        } => {
-            eprintln!("jobcan email is required.");
-            eprintln!("jobcan password is required.");
-            error_exit();
+    }
+
+    if deleted {
+        println!("Deleted the password of {}.", email);
+    } else {
+        println!("No password of {} is saved.", email);
+    }
+}
+
+fn jobcan_from_cli(credentials: cli::Credentials, context: &Context) -> Jobcan {
+    jobcan_from_account(account_from_cli(credentials, context), context)
+}
+
+fn jobcan_from_account(account: Account, context: &Context) -> Jobcan {
+    let endpoints = &context.endpoints;
+    let session_file = session_file(account.email());
+
+    let mut builder = Jobcan::builder(account);
+    if let Some(url) = &endpoints.id_url {
+        builder = builder.id_url(url);
+    }
+    if let Some(url) = &endpoints.ssl_url {
+        builder = builder.ssl_url(url);
+    }
+    if let Some(path) = session_file {
+        builder = builder.session_file(path);
+    }
+    builder = builder.retry(context.retry);
+    builder = http_options(builder, &context.http);
+
+    builder.build().unwrap_or_else(|e| error_exit(e))
+}
+
+/// Flags and environment variables take precedence over the config file.
+fn endpoints_from_cli(endpoints: cli::Endpoints, config: config::Endpoints) -> cli::Endpoints {
+    cli::Endpoints {
+        id_url: endpoints.id_url.or(config.id_url),
+        ssl_url: endpoints.ssl_url.or(config.ssl_url),
+    }
+}
+
+/// Flags and environment variables take precedence over the config file.
+fn http_from_cli(http: cli::Http, config: config::Http) -> cli::Http {
+    cli::Http {
+        connect_timeout: http.connect_timeout.or(config.connect_timeout),
+        timeout: http.timeout.or(config.timeout),
+        proxy: http.proxy.or(config.proxy),
+        proxy_user: http.proxy_user.or(config.proxy_user),
+        ca_cert: if http.ca_cert.is_empty() {
+            config.ca_certs
+        } else {
+            http.ca_cert
+        },
+        user_agent: http.user_agent.or(config.user_agent),
+    }
+}
+
+fn http_options(mut builder: JobcanBuilder, http: &cli::Http) -> JobcanBuilder {
+    if let Some(secs) = http.connect_timeout {
+        builder = builder.connect_timeout(Duration::from_secs(secs));
+    }
+    if let Some(secs) = http.timeout {
+        builder = builder.timeout(Duration::from_secs(secs));
+    }
+    if let Some(url) = &http.proxy {
+        builder = builder.proxy(url);
+    }
+    if let Some(user) = &http.proxy_user {
+        // Note: Only from the environment variable, never from arguments or the config file
+        let password = env::var(PROXY_PASSWORD_ENV).unwrap_or_default();
+        builder = builder.proxy_credentials(user, password);
+    }
+    for path in &http.ca_cert {
+        let pem = fs::read(path).unwrap_or_else(|e| {
+            error_exit(Failure::new(
+                ExitCode::ConfigError,
+                format!("Failed to read {}: {}", path.display(), e),
+            ))
+        });
+        builder = builder.add_root_certificate(pem);
+    }
+    if let Some(user_agent) = &http.user_agent {
+        builder = builder.user_agent(user_agent);
+    }
+    builder
+}
+
+/// The flag and environment variable take precedence over the config file.
+fn retry_from_cli(retries: Option<u32>, config: config::Retry) -> RetryPolicy {
+    let default = RetryPolicy::default();
+    RetryPolicy {
+        max_retries: retries
+            .or(config.max_retries)
+            .unwrap_or(default.max_retries),
+        initial_backoff: config
+            .initial_backoff_ms
+            .map_or(default.initial_backoff, Duration::from_millis),
+        max_backoff: config
+            .max_backoff_ms
+            .map_or(default.max_backoff, Duration::from_millis),
+    }
+}
+
+/// Session cookies are kept per account so that switching `--email` never reuses
+/// another user's login.
+fn session_file(email: &str) -> Option<PathBuf> {
+    let dir = dirs::state_dir().or_else(dirs::cache_dir)?;
+    Some(dir.join("jobcan-cli").join(session_file_name(email)))
+}
+
+/// File name of the session of `email`. Bytes other than `[A-Za-z0-9@.-]` are percent-encoded,
+/// so that different emails never share a file.
+fn session_file_name(email: &str) -> String {
+    let name: String = email
+        .bytes()
+        .map(|b| match b {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'@' | b'.' | b'-' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect();
+    format!("session-{}.json", name)
+}
+
+fn account_from_cli(credentials: cli::Credentials, context: &Context) -> Account {
+    let email = email_from_cli(credentials.email, context);
+    let password = password_from_cli(
+        credentials.password,
+        &credentials.password_source,
+        email.as_deref(),
+        Some(context.credential_store),
+    );
+
+    match (email, password) {
+        (Some(email), Some(password)) => Account::new(email, password),
+        (Some(_), None) => error_exit(Failure::new(
+            ExitCode::MissingCredentials,
+            "jobcan password is required.",
+        )),
+        (None, Some(_)) => error_exit(Failure::new(
+            ExitCode::MissingCredentials,
+            "jobcan email is required.",
+        )),
+        (None, None) => error_exit(Failure::new(
+            ExitCode::MissingCredentials,
+            "jobcan email is required.\njobcan password is required.",
+        )),
+    }
+}
+
+fn email_from_cli(email: Option<String>, context: &Context) -> Option<String> {
+    email.or_else(|| context.profile.email.clone())
+}
+
+/// Password from `--password-stdin`/`--password-file`/`--password-command`, `--password` or
+/// `$JOBCAN_PASSWORD`, `store`, or a prompt on the terminal, in this order.
+fn password_from_cli(
+    password: Option<String>,
+    source: &cli::PasswordSource,
+    email: Option<&str>,
+    store: Option<CredentialStore>,
+) -> Option<SecretString> {
+    let missing =
+        |e: String| -> SecretString { error_exit(Failure::new(ExitCode::MissingCredentials, e)) };
+
+    if let Some(password) = password::from_source(source) {
+        return Some(password.unwrap_or_else(missing));
+    }
+    if let Some(password) = password {
+        return Some(SecretString::new(password));
+    }
+
+    let email = email?;
+    if let Some(store) = store {
+        let saved = store
+            .get(email)
+            .unwrap_or_else(|e| error_exit(Failure::new(ExitCode::CredentialStoreError, e)));
+        if saved.is_some() {
+            return saved;
         }
     }
+    password::prompt(email).map(|password| password.unwrap_or_else(missing))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_file_names_of_different_emails_differ() {
+        // Arrange
+        let emails = ["a+b@x", "a_b@x", "a%2Bb@x"];
+
+        // Act
+        let names: Vec<String> = emails
+            .iter()
+            .map(|email| session_file_name(email))
+            .collect();
+
+        // Assert
+        assert!(
+            names
+                == [
+                    "session-a%2Bb@x.json",
+                    "session-a%5Fb@x.json",
+                    "session-a%252Bb@x.json"
+                ]
+        );
+    }
 }
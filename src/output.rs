@@ -0,0 +1,47 @@
+use std::io;
+
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    /// Human readable text
+    #[default]
+    Text,
+    /// A JSON document
+    Json,
+    /// Comma-separated values with a header row
+    Csv,
+    /// Tab-separated values with a header row
+    Tsv,
+}
+
+impl OutputFormat {
+    /// Print `document` as JSON, `rows` as CSV/TSV, or call `text` to print plain text.
+    pub fn print<D, R>(self, document: &D, rows: &[R], text: impl FnOnce())
+    where
+        D: Serialize + ?Sized,
+        R: Serialize,
+    {
+        match self {
+            OutputFormat::Text => text(),
+            OutputFormat::Json => {
+                let json = serde_json::to_string_pretty(document).expect("Failed to write JSON");
+                println!("{}", json);
+            }
+            OutputFormat::Csv => Self::print_rows(rows, b','),
+            OutputFormat::Tsv => Self::print_rows(rows, b'\t'),
+        }
+    }
+
+    fn print_rows<R: Serialize>(rows: &[R], delimiter: u8) {
+        let mut writer = csv::WriterBuilder::new()
+            .delimiter(delimiter)
+            .from_writer(io::stdout());
+        for row in rows {
+            writer.serialize(row).expect("Failed to write rows");
+        }
+        writer.flush().expect("Failed to write rows");
+    }
+}
@@ -0,0 +1,48 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::{approval_state::ApprovalState, clock_time::ClockTime};
+
+/// An overtime application (残業申請) to submit with [`crate::Jobcan::request_overtime`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OvertimeRequest {
+    pub date: NaiveDate,
+    /// Time the overtime is planned to end, past 23:59 for an overtime ending after midnight.
+    pub end_time: ClockTime,
+    /// Reason of the overtime (申請理由), which Jobcan requires.
+    pub reason: String,
+}
+
+/// An overtime application listed on the overtime page, with its state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OvertimeApplication {
+    pub(crate) date: NaiveDate,
+    pub(crate) end_time: String,
+    pub(crate) overtime: Option<String>,
+    pub(crate) reason: Option<String>,
+    pub(crate) state: ApprovalState,
+}
+
+impl OvertimeApplication {
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Planned end time in `HH:MM`. The hour exceeds 23 past midnight.
+    pub fn end_time(&self) -> &str {
+        &self.end_time
+    }
+
+    /// Planned overtime as Jobcan shows it, e.g. `1:30`.
+    pub fn overtime(&self) -> Option<&str> {
+        self.overtime.as_deref()
+    }
+
+    pub fn reason(&self) -> Option<&str> {
+        self.reason.as_deref()
+    }
+
+    pub fn state(&self) -> ApprovalState {
+        self.state
+    }
+}
@@ -0,0 +1,96 @@
+use std::{
+    fs,
+    io::{self, BufRead, IsTerminal},
+    path::Path,
+    process::{Command, Stdio},
+};
+
+use jobcan::SecretString;
+use secrecy::zeroize::Zeroizing;
+
+use crate::cli::PasswordSource;
+
+/// Read the password from the source given by flags, or `None` if no source is given.
+pub fn from_source(source: &PasswordSource) -> Option<Result<SecretString, String>> {
+    if source.password_stdin {
+        Some(from_stdin())
+    } else if let Some(path) = &source.password_file {
+        Some(from_file(path))
+    } else {
+        source.password_command.as_deref().map(from_command)
+    }
+}
+
+/// Ask the password on the terminal without echo, or `None` if stdin is not a terminal,
+/// e.g. in cron jobs and pipes.
+pub fn prompt(email: &str) -> Option<Result<SecretString, String>> {
+    if !io::stdin().is_terminal() {
+        return None;
+    }
+
+    let password = rpassword::prompt_password(format!("Jobcan password for {}: ", email))
+        .map(Zeroizing::new)
+        .map_err(|e| format!("Failed to read the password: {}", e));
+    Some(password.and_then(|password| non_empty(first_line(&password))))
+}
+
+fn from_stdin() -> Result<SecretString, String> {
+    let mut line = Zeroizing::new(String::new());
+    io::stdin()
+        .lock()
+        .read_line(&mut line)
+        .map_err(|e| format!("Failed to read the password from stdin: {}", e))?;
+    non_empty(first_line(&line))
+}
+
+fn from_file(path: &Path) -> Result<SecretString, String> {
+    let text = fs::read_to_string(path)
+        .map(Zeroizing::new)
+        .map_err(|e| format!("Failed to read the password from {}: {}", path.display(), e))?;
+    non_empty(first_line(&text))
+}
+
+fn from_command(command: &str) -> Result<SecretString, String> {
+    let output = shell(command)
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("Failed to run the password command: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "The password command failed with {}",
+            output.status
+        ));
+    }
+
+    let stdout = Zeroizing::new(output.stdout);
+    let stdout = std::str::from_utf8(&stdout)
+        .map_err(|_| "The password command printed non UTF-8 text".to_string())?;
+    non_empty(first_line(stdout))
+}
+
+#[cfg(unix)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("sh");
+    shell.arg("-c").arg(command);
+    shell
+}
+
+#[cfg(windows)]
+fn shell(command: &str) -> Command {
+    let mut shell = Command::new("cmd");
+    shell.arg("/C").arg(command);
+    shell
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().into()
+}
+
+fn non_empty(password: String) -> Result<SecretString, String> {
+    if password.is_empty() {
+        Err("jobcan password is required.".into())
+    } else {
+        Ok(SecretString::new(password))
+    }
+}
@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use rand::Rng;
+use reqwest::StatusCode;
+
+/// How requests to Jobcan are retried on connection errors, timeouts and 5xx responses.
+///
+/// The `n`th retry waits `initial_backoff * 2^(n - 1)`, capped at `max_backoff`, of which
+/// up to half is random jitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Never retry.
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_retries: 0,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+    };
+
+    /// Time to wait before the `retry`th retry, starting from 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponential = self
+            .initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)));
+        let backoff = exponential.min(self.max_backoff);
+        let jitter = rand::thread_rng().gen_range(0.0..=0.5);
+        backoff.mul_f64(1.0 - jitter)
+    }
+
+    pub(crate) fn is_transient_error(error: &reqwest::Error) -> bool {
+        error.is_connect() || error.is_timeout()
+    }
+
+    pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+        status.is_server_error()
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(8),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backoff_grows_exponentially_with_jitter() {
+        // Arrange
+        let policy = RetryPolicy::default();
+
+        // Act
+        let backoffs: Vec<Duration> = (1..=3).map(|retry| policy.backoff(retry)).collect();
+
+        // Assert
+        for (backoff, max) in backoffs.iter().zip([500, 1000, 2000]) {
+            assert!(*backoff >= Duration::from_millis(max / 2));
+            assert!(*backoff <= Duration::from_millis(max));
+        }
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        // Arrange
+        let policy = RetryPolicy::default();
+
+        // Act
+        let backoff = policy.backoff(100);
+
+        // Assert
+        assert!(backoff >= Duration::from_secs(4));
+        assert!(backoff <= Duration::from_secs(8));
+    }
+}
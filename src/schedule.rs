@@ -0,0 +1,464 @@
+use chrono::{Datelike, Days, NaiveDate, NaiveDateTime, NaiveTime, Weekday};
+use jobcan::Stamp;
+use serde::Deserialize;
+
+use crate::calendar::Calendar;
+
+/// Weekly schedule of `daemon`, the `[schedule]` table of the config file. Weekdays without
+/// a table are days off.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Schedule {
+    /// Dates to skip in addition to holidays.
+    #[serde(default)]
+    pub skip_dates: Vec<NaiveDate>,
+    /// Whether days off of the `[calendar]` are skipped. On by default.
+    #[serde(default = "default_skip_holidays")]
+    pub skip_holidays: bool,
+    pub monday: Option<ScheduleDay>,
+    pub tuesday: Option<ScheduleDay>,
+    pub wednesday: Option<ScheduleDay>,
+    pub thursday: Option<ScheduleDay>,
+    pub friday: Option<ScheduleDay>,
+    pub saturday: Option<ScheduleDay>,
+    pub sunday: Option<ScheduleDay>,
+}
+
+fn default_skip_holidays() -> bool {
+    true
+}
+
+/// Stamps of a weekday. Options which are not set fall back to the profile.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ScheduleDay {
+    pub clock_in: Option<NaiveTime>,
+    pub clock_out: Option<NaiveTime>,
+    #[serde(default)]
+    pub breaks: Vec<Break>,
+    pub group: Option<String>,
+    pub group_id: Option<String>,
+    pub notes: Option<String>,
+    pub night_shift: Option<bool>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Break {
+    pub start: NaiveTime,
+    pub end: NaiveTime,
+}
+
+/// A stamp of the schedule at a point in time.
+#[derive(Debug)]
+pub struct Event<'a> {
+    pub at: NaiveDateTime,
+    pub stamp: Stamp,
+    pub day: &'a ScheduleDay,
+}
+
+/// Days looked ahead for the next event, so that long runs of skipped dates are passed over.
+const LOOKAHEAD_DAYS: u64 = 366;
+
+impl Default for Schedule {
+    fn default() -> Self {
+        Schedule {
+            skip_dates: Vec::new(),
+            skip_holidays: default_skip_holidays(),
+            monday: None,
+            tuesday: None,
+            wednesday: None,
+            thursday: None,
+            friday: None,
+            saturday: None,
+            sunday: None,
+        }
+    }
+}
+
+impl Schedule {
+    pub fn day(&self, weekday: Weekday) -> Option<&ScheduleDay> {
+        match weekday {
+            Weekday::Mon => self.monday.as_ref(),
+            Weekday::Tue => self.tuesday.as_ref(),
+            Weekday::Wed => self.wednesday.as_ref(),
+            Weekday::Thu => self.thursday.as_ref(),
+            Weekday::Fri => self.friday.as_ref(),
+            Weekday::Sat => self.saturday.as_ref(),
+            Weekday::Sun => self.sunday.as_ref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        WEEKDAYS
+            .iter()
+            .all(|(weekday, _)| self.day(*weekday).is_none_or(|day| day.stamps().is_empty()))
+    }
+
+    /// Check that the stamps of each day are in order within 24 hours, e.g. a break starts
+    /// after clock-in. Stamps can run past midnight only on a night shift, which falls back to
+    /// `night_shift` of the profile, so that a typo isn't taken for one.
+    pub fn validate(&self, night_shift: Option<bool>) -> Result<(), String> {
+        for (weekday, name) in WEEKDAYS {
+            let Some(day) = self.day(weekday) else {
+                continue;
+            };
+            let timeline = day.timeline();
+            let within_a_day = match (timeline.first(), timeline.last()) {
+                (Some(first), Some(last)) => last.0 == 0 || (last.0 == 1 && last.1 < first.1),
+                _ => true,
+            };
+            if !within_a_day || timeline.windows(2).any(|pair| pair[0].1 == pair[1].1) {
+                return Err(format!(
+                    "Stamps of {} in the schedule must be in order of clock_in, breaks and clock_out within 24 hours.",
+                    name
+                ));
+            }
+            let past_midnight = timeline.last().is_some_and(|last| last.0 > 0);
+            if past_midnight && !day.night_shift.or(night_shift).unwrap_or(false) {
+                return Err(format!(
+                    "Stamps of {} in the schedule run past midnight. Set night_shift = true for a night shift.",
+                    name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Stamps on `date` in order, or none if the date is skipped or a day off of `calendar`.
+    pub fn events_on(&self, date: NaiveDate, calendar: &Calendar) -> Vec<Event<'_>> {
+        if self.skip_dates.contains(&date) || calendar.holiday(date).is_some() {
+            return Vec::new();
+        }
+        let Some(day) = self.day(date.weekday()) else {
+            return Vec::new();
+        };
+        day.timeline()
+            .into_iter()
+            .filter_map(|(days, time, stamp)| {
+                Some(Event {
+                    at: date.checked_add_days(Days::new(days))?.and_time(time),
+                    stamp,
+                    day,
+                })
+            })
+            .collect()
+    }
+
+    /// The first stamp strictly after `after`.
+    pub fn next_event(&self, after: NaiveDateTime, calendar: &Calendar) -> Option<Event<'_>> {
+        // Note: Starts from the day before, whose night shift may still be running
+        let start = after.date().pred_opt()?;
+        let mut days = (0..=LOOKAHEAD_DAYS)
+            .filter_map(|days| start.checked_add_days(Days::new(days)))
+            .map(|date| {
+                let mut events = self.events_on(date, calendar);
+                events.retain(|event| event.at > after);
+                events
+            });
+        let first = days.find(|events| !events.is_empty())?;
+        // Note: A night shift can overlap the stamps of the next day, which may come first
+        let next = days.next().unwrap_or_default();
+        first.into_iter().chain(next).min_by_key(|event| event.at)
+    }
+}
+
+impl ScheduleDay {
+    /// Stamps of the day in the order they are expected, which `Schedule::validate` checks.
+    fn stamps(&self) -> Vec<(NaiveTime, Stamp)> {
+        let breaks = self
+            .breaks
+            .iter()
+            .flat_map(|b| [(b.start, Stamp::StartBreak), (b.end, Stamp::EndBreak)]);
+        self.clock_in
+            .map(|time| (time, Stamp::ClockIn))
+            .into_iter()
+            .chain(breaks)
+            .chain(self.clock_out.map(|time| (time, Stamp::ClockOut)))
+            .collect()
+    }
+
+    /// Stamps of the day with the number of days after the date each is on. A stamp earlier
+    /// than the one before it is on the next day, e.g. clock-out of a night shift.
+    fn timeline(&self) -> Vec<(u64, NaiveTime, Stamp)> {
+        let mut days = 0;
+        let mut last = None;
+        self.stamps()
+            .into_iter()
+            .map(|(time, stamp)| {
+                if last.is_some_and(|last| time < last) {
+                    days += 1;
+                }
+                last = Some(time);
+                (days, time, stamp)
+            })
+            .collect()
+    }
+}
+
+/// Weekdays with their keys in the config file.
+const WEEKDAYS: [(Weekday, &str); 7] = [
+    (Weekday::Mon, "monday"),
+    (Weekday::Tue, "tuesday"),
+    (Weekday::Wed, "wednesday"),
+    (Weekday::Thu, "thursday"),
+    (Weekday::Fri, "friday"),
+    (Weekday::Sat, "saturday"),
+    (Weekday::Sun, "sunday"),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calendar::CalendarConfig;
+
+    fn time(text: &str) -> NaiveTime {
+        text.parse().unwrap()
+    }
+
+    fn weekday_schedule() -> Schedule {
+        let day = || ScheduleDay {
+            clock_in: Some(time("09:00")),
+            clock_out: Some(time("18:00")),
+            breaks: vec![Break {
+                start: time("12:00"),
+                end: time("13:00"),
+            }],
+            ..Default::default()
+        };
+        Schedule {
+            monday: Some(day()),
+            tuesday: Some(day()),
+            wednesday: Some(day()),
+            thursday: Some(day()),
+            friday: Some(day()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn events_on_weekday() {
+        // Arrange
+        let schedule = weekday_schedule();
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 8).unwrap();
+
+        // Act
+        let events = schedule.events_on(monday, &Calendar::default());
+
+        // Assert
+        let stamps: Vec<(NaiveTime, Stamp)> = events
+            .iter()
+            .map(|event| (event.at.time(), event.stamp))
+            .collect();
+        assert!(
+            stamps
+                == vec![
+                    (time("09:00"), Stamp::ClockIn),
+                    (time("12:00"), Stamp::StartBreak),
+                    (time("13:00"), Stamp::EndBreak),
+                    (time("18:00"), Stamp::ClockOut),
+                ]
+        );
+    }
+
+    #[test]
+    fn next_event_on_same_day() {
+        // Arrange
+        let schedule = weekday_schedule();
+        let after = NaiveDate::from_ymd_opt(2024, 1, 8)
+            .unwrap()
+            .and_time(time("12:00"));
+
+        // Act
+        let event = schedule.next_event(after, &Calendar::default()).unwrap();
+
+        // Assert
+        assert!(event.at == after.date().and_time(time("13:00")));
+        assert!(event.stamp == Stamp::EndBreak);
+    }
+
+    #[test]
+    fn next_event_skips_days_off_and_skipped_dates() {
+        // Arrange
+        let mut schedule = weekday_schedule();
+        schedule.skip_dates = vec![NaiveDate::from_ymd_opt(2024, 1, 8).unwrap()];
+        let friday_evening = NaiveDate::from_ymd_opt(2024, 1, 5)
+            .unwrap()
+            .and_time(time("18:00"));
+
+        // Act
+        let event = schedule
+            .next_event(friday_evening, &Calendar::default())
+            .unwrap();
+
+        // Assert
+        let tuesday = NaiveDate::from_ymd_opt(2024, 1, 9).unwrap();
+        assert!(event.at == tuesday.and_time(time("09:00")));
+        assert!(event.stamp == Stamp::ClockIn);
+    }
+
+    #[test]
+    fn next_event_skips_holidays() {
+        // Arrange
+        let schedule = weekday_schedule();
+        let calendar = Calendar::load(&CalendarConfig::default()).unwrap();
+        let friday_evening = NaiveDate::from_ymd_opt(2024, 1, 5)
+            .unwrap()
+            .and_time(time("18:00"));
+
+        // Act
+        let event = schedule.next_event(friday_evening, &calendar).unwrap();
+
+        // Assert
+        // Note: 2024-01-08 is Coming of Age Day
+        let tuesday = NaiveDate::from_ymd_opt(2024, 1, 9).unwrap();
+        assert!(event.at == tuesday.and_time(time("09:00")));
+    }
+
+    #[test]
+    fn next_event_without_schedule() {
+        // Arrange
+        let schedule = Schedule::default();
+        let now = NaiveDate::from_ymd_opt(2024, 1, 8)
+            .unwrap()
+            .and_time(time("09:00"));
+
+        // Act
+        let event = schedule.next_event(now, &Calendar::default());
+
+        // Assert
+        assert!(event.is_none());
+        assert!(schedule.is_empty());
+    }
+
+    #[test]
+    fn validate_stamps_out_of_order() {
+        // Arrange
+        let mut schedule = weekday_schedule();
+        schedule.friday.as_mut().unwrap().clock_out = Some(time("11:00"));
+
+        // Act
+        let result = schedule.validate(None);
+
+        // Assert
+        assert!(
+            result.unwrap_err()
+                == "Stamps of friday in the schedule must be in order of clock_in, breaks and clock_out within 24 hours."
+        );
+    }
+
+    fn night_shift_schedule() -> Schedule {
+        Schedule {
+            monday: Some(ScheduleDay {
+                clock_in: Some(time("22:00")),
+                clock_out: Some(time("06:00")),
+                breaks: vec![Break {
+                    start: time("02:00"),
+                    end: time("03:00"),
+                }],
+                night_shift: Some(true),
+                ..Default::default()
+            }),
+            tuesday: Some(ScheduleDay {
+                clock_in: Some(time("22:00")),
+                clock_out: Some(time("06:00")),
+                night_shift: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn events_of_night_shift_past_midnight() {
+        // Arrange
+        let schedule = night_shift_schedule();
+        let monday = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let tuesday = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+
+        // Act
+        let events = schedule.events_on(monday, &Calendar::default());
+
+        // Assert
+        let stamps: Vec<(NaiveDateTime, Stamp)> =
+            events.iter().map(|event| (event.at, event.stamp)).collect();
+        assert!(schedule.validate(None).is_ok());
+        assert!(
+            stamps
+                == vec![
+                    (monday.and_time(time("22:00")), Stamp::ClockIn),
+                    (tuesday.and_time(time("02:00")), Stamp::StartBreak),
+                    (tuesday.and_time(time("03:00")), Stamp::EndBreak),
+                    (tuesday.and_time(time("06:00")), Stamp::ClockOut),
+                ]
+        );
+    }
+
+    #[test]
+    fn next_event_of_night_shift_from_day_before() {
+        // Arrange
+        let schedule = night_shift_schedule();
+        let tuesday = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+
+        // Act
+        let event = schedule
+            .next_event(tuesday.and_time(time("03:00")), &Calendar::default())
+            .unwrap();
+
+        // Assert
+        assert!(event.at == tuesday.and_time(time("06:00")));
+        assert!(event.stamp == Stamp::ClockOut);
+    }
+
+    #[test]
+    fn validate_past_midnight_without_night_shift() {
+        // Arrange
+        let mut schedule = weekday_schedule();
+        schedule.monday.as_mut().unwrap().clock_out = Some(time("08:00"));
+        schedule.monday.as_mut().unwrap().breaks = Vec::new();
+
+        // Act
+        let result = schedule.validate(None);
+
+        // Assert
+        assert!(
+            result.unwrap_err()
+                == "Stamps of monday in the schedule run past midnight. Set night_shift = true for a night shift."
+        );
+    }
+
+    #[test]
+    fn validate_past_midnight_with_night_shift_of_profile() {
+        // Arrange
+        let mut schedule = weekday_schedule();
+        schedule.monday.as_mut().unwrap().clock_in = Some(time("22:00"));
+        schedule.monday.as_mut().unwrap().clock_out = Some(time("06:00"));
+        schedule.monday.as_mut().unwrap().breaks = Vec::new();
+
+        // Act
+        let result = schedule.validate(Some(true));
+
+        // Assert
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn validate_night_shift_longer_than_a_day() {
+        // Arrange
+        let mut schedule = night_shift_schedule();
+        schedule.tuesday.as_mut().unwrap().clock_out = Some(time("23:00"));
+        schedule.tuesday.as_mut().unwrap().breaks = vec![Break {
+            start: time("02:00"),
+            end: time("03:00"),
+        }];
+
+        // Act
+        let result = schedule.validate(None);
+
+        // Assert
+        assert!(
+            result.unwrap_err()
+                == "Stamps of tuesday in the schedule must be in order of clock_in, breaks and clock_out within 24 hours."
+        );
+    }
+}
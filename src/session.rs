@@ -0,0 +1,184 @@
+use std::{
+    fs,
+    io::{self, BufReader, Write},
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+use reqwest::{cookie::CookieStore, header::HeaderValue, Url};
+
+use crate::{error::JobcanError, Result};
+
+/// Cookie jar shared with `reqwest::Client` that can be saved to and restored from a file,
+/// so that a login session survives between invocations.
+pub struct Session {
+    store: Mutex<cookie_store::CookieStore>,
+}
+
+impl Session {
+    pub fn new() -> Session {
+        Session {
+            store: Mutex::new(cookie_store::CookieStore::default()),
+        }
+    }
+
+    /// Load cookies saved by [`Session::save`]. A missing or unparsable file yields an empty
+    /// session.
+    pub fn load(path: &Path) -> Result<Session> {
+        let file = match fs::File::open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Session::new()),
+            Err(e) => return Err(Self::io_error("Failed to open session file", path, e)),
+        };
+
+        // Note: The file is only a cache of the login, so a broken one just means logging in
+        // again, which also replaces it
+        let Ok(cookies) =
+            serde_json::from_reader::<_, Vec<cookie_store::Cookie<'static>>>(BufReader::new(file))
+        else {
+            return Ok(Session::new());
+        };
+        let store = cookie_store::CookieStore::from_cookies(
+            cookies.into_iter().map(Ok::<_, JobcanError>),
+            false,
+        )?;
+
+        Ok(Session {
+            store: Mutex::new(store),
+        })
+    }
+
+    /// Save all unexpired cookies, including session cookies which `cookie_store` would
+    /// otherwise drop, because Jobcan keeps the login state in a session cookie.
+    ///
+    /// The file is replaced atomically, so that overlapping invocations never leave it torn.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let cookies = {
+            let store = self.store.lock().unwrap();
+            serde_json::to_vec(&store.iter_unexpired().collect::<Vec<_>>())
+                .map_err(|e| Self::io_error("Failed to serialize session", path, e.into()))?
+        };
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| Self::io_error("Failed to create session directory", path, e))?;
+        }
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        // Note: The temporary file is in the same directory, so that the rename doesn't cross
+        // file systems
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(format!(".{}.tmp", std::process::id()));
+        let temp_path = PathBuf::from(temp_path);
+        options
+            .open(&temp_path)
+            .and_then(|mut file| file.write_all(&cookies))
+            .and_then(|()| fs::rename(&temp_path, path))
+            .map_err(|e| {
+                let _ = fs::remove_file(&temp_path);
+                Self::io_error("Failed to write session file", path, e)
+            })
+    }
+
+    fn io_error(message: &str, path: &Path, raw_error: io::Error) -> JobcanError {
+        JobcanError::SessionError {
+            message: message.into(),
+            path: PathBuf::from(path),
+            raw_error,
+        }
+    }
+}
+
+impl Default for Session {
+    fn default() -> Self {
+        Session::new()
+    }
+}
+
+impl CookieStore for Session {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let mut store = self.store.lock().unwrap();
+        for header in cookie_headers {
+            if let Ok(cookie) = header.to_str() {
+                // Note: Malformed or rejected cookies are ignored as browsers do
+                let _ = store.parse(cookie, url);
+            }
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let s = self
+            .store
+            .lock()
+            .unwrap()
+            .get_request_values(url)
+            .map(|(name, value)| format!("{}={}", name, value))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        if s.is_empty() {
+            return None;
+        }
+
+        HeaderValue::from_str(&s).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn save_and_load_keep_session_cookies() {
+        // Arrange
+        let path = std::env::temp_dir().join(format!("jobcan-session-{}.json", std::process::id()));
+        let url = Url::parse("https://ssl.jobcan.jp/employee").unwrap();
+        let session = Session::new();
+        let header = HeaderValue::from_static("sid=abc; Path=/");
+        session.set_cookies(&mut std::iter::once(&header), &url);
+
+        // Act
+        session.save(&path).unwrap();
+        let loaded = Session::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // Assert
+        assert!(loaded.cookies(&url).unwrap() == "sid=abc");
+    }
+
+    #[test]
+    fn load_broken_file_yields_empty_session() {
+        // Arrange
+        let path =
+            std::env::temp_dir().join(format!("jobcan-session-broken-{}.json", std::process::id()));
+        fs::write(&path, r#"[{"raw_cookie":"sid=abc"#).unwrap();
+        let url = Url::parse("https://ssl.jobcan.jp/employee").unwrap();
+
+        // Act
+        let session = Session::load(&path);
+        fs::remove_file(&path).unwrap();
+
+        // Assert
+        assert!(session.unwrap().cookies(&url).is_none());
+    }
+
+    #[test]
+    fn load_missing_file_yields_empty_session() {
+        // Arrange
+        let path = std::env::temp_dir().join("jobcan-session-does-not-exist.json");
+        let url = Url::parse("https://ssl.jobcan.jp/employee").unwrap();
+
+        // Act
+        let session = Session::load(&path).unwrap();
+
+        // Assert
+        assert!(session.cookies(&url).is_none());
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDate;
+use serde::Serialize;
+
+/// A day on the shift page (シフト表) with its planned start and end.
+///
+/// Days without a shift, such as days off, have neither start nor end.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Shift {
+    pub(crate) date: NaiveDate,
+    pub(crate) start: Option<String>,
+    pub(crate) end: Option<String>,
+    pub(crate) group: Option<String>,
+}
+
+impl Shift {
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    /// Planned start in `HH:MM`.
+    pub fn start(&self) -> Option<&str> {
+        self.start.as_deref()
+    }
+
+    /// Planned end in `HH:MM`. The hour exceeds 23 for a night shift.
+    pub fn end(&self) -> Option<&str> {
+        self.end.as_deref()
+    }
+
+    /// Group the shift is assigned to.
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+}
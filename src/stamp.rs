@@ -1,8 +1,12 @@
 use std::fmt::Display;
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, PartialEq, Eq)]
+use crate::working_status::WorkingStatus;
+
+/// Kinds of stamp a Jobcan employee can make.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Stamp {
     ClockIn,
     ClockOut,
@@ -18,11 +22,45 @@ impl Display for Stamp {
             Stamp::StartBreak => "StartBreak",
             Stamp::EndBreak => "EndBreak",
         };
-        write!(f, "{}", s)
+        f.pad(s)
     }
 }
 
 impl Stamp {
+    /// Parse the label Jobcan shows for a stamp, e.g. `出勤`.
+    pub fn from_label(label: &str) -> Option<Stamp> {
+        match label {
+            "出勤" => Some(Stamp::ClockIn),
+            "退勤" => Some(Stamp::ClockOut),
+            "休憩開始" | "休憩入り" => Some(Stamp::StartBreak),
+            "休憩終了" | "休憩戻り" => Some(Stamp::EndBreak),
+            _ => None,
+        }
+    }
+
+    /// Whether Jobcan accepts this stamp in `status`. Clocking out ends a break as well.
+    pub fn is_allowed_in(&self, status: &WorkingStatus) -> bool {
+        matches!(
+            (self, status),
+            (Stamp::ClockIn, WorkingStatus::NotWorking)
+                | (
+                    Stamp::ClockOut,
+                    WorkingStatus::Working | WorkingStatus::Resting
+                )
+                | (Stamp::StartBreak, WorkingStatus::Working)
+                | (Stamp::EndBreak, WorkingStatus::Resting)
+        )
+    }
+
+    /// Working status after Jobcan accepted this stamp.
+    pub fn status_after(&self) -> WorkingStatus {
+        match self {
+            Stamp::ClockIn | Stamp::EndBreak => WorkingStatus::Working,
+            Stamp::ClockOut => WorkingStatus::NotWorking,
+            Stamp::StartBreak => WorkingStatus::Resting,
+        }
+    }
+
     pub fn to_request_params(&self) -> String {
         match self {
             Stamp::ClockIn => "work_start".to_string(),
@@ -54,6 +92,7 @@ impl Stamp {
     }
 }
 
+/// JSON body Jobcan answers a stamp request with.
 #[derive(Debug, Deserialize)]
 pub struct Response {
     #[allow(dead_code)] // Note: Use json deserialization
@@ -79,3 +118,24 @@ impl PartialEq for Response {
         self.current_status == other.current_status
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clock_in_is_allowed_only_while_not_working() {
+        // Assert
+        assert!(Stamp::ClockIn.is_allowed_in(&WorkingStatus::NotWorking));
+        assert!(!Stamp::ClockIn.is_allowed_in(&WorkingStatus::Working));
+        assert!(!Stamp::ClockIn.is_allowed_in(&WorkingStatus::Resting));
+    }
+
+    #[test]
+    fn end_break_is_allowed_only_while_resting() {
+        // Assert
+        assert!(Stamp::EndBreak.is_allowed_in(&WorkingStatus::Resting));
+        assert!(!Stamp::EndBreak.is_allowed_in(&WorkingStatus::Working));
+        assert!(!Stamp::EndBreak.is_allowed_in(&WorkingStatus::NotWorking));
+    }
+}
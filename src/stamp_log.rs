@@ -0,0 +1,144 @@
+use chrono::{Duration, NaiveTime, Timelike};
+use serde::Serialize;
+
+use crate::stamp::Stamp;
+
+/// A stamp made today, as listed on the employee page.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StampLog {
+    pub(crate) time: String,
+    pub(crate) stamp: Stamp,
+    pub(crate) group: Option<String>,
+    pub(crate) note: Option<String>,
+}
+
+/// Work and break time accumulated by today's stamps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Elapsed {
+    pub work: Duration,
+    pub rest: Duration,
+}
+
+impl StampLog {
+    /// Time of the stamp in `HH:MM`. The hour exceeds 23 for a night shift.
+    pub fn time(&self) -> &str {
+        &self.time
+    }
+
+    pub fn stamp(&self) -> Stamp {
+        self.stamp
+    }
+
+    pub fn group(&self) -> Option<&str> {
+        self.group.as_deref()
+    }
+
+    pub fn note(&self) -> Option<&str> {
+        self.note.as_deref()
+    }
+
+    /// Sum up work and break time of `logs` in order, counting the ongoing work or break
+    /// until `now`.
+    pub fn elapsed(logs: &[StampLog], now: NaiveTime) -> Elapsed {
+        let mut elapsed = Elapsed {
+            work: Duration::zero(),
+            rest: Duration::zero(),
+        };
+        let mut add = |stamp: Stamp, minutes: u32| {
+            let duration = Duration::minutes(minutes.into());
+            match stamp {
+                Stamp::ClockIn | Stamp::EndBreak => elapsed.work += duration,
+                Stamp::StartBreak => elapsed.rest += duration,
+                Stamp::ClockOut => {}
+            }
+        };
+
+        let mut last: Option<(Stamp, u32)> = None;
+        for log in logs {
+            let minutes = log.minutes();
+            if let Some((stamp, since)) = last {
+                add(stamp, minutes.saturating_sub(since));
+            }
+            last = Some((log.stamp, minutes));
+        }
+
+        if let Some((stamp, since)) = last {
+            let mut now = now.hour() * 60 + now.minute();
+            // Note: Past midnight, a night shift is still counted as today
+            while now < since {
+                now += 24 * 60;
+            }
+            add(stamp, now - since);
+        }
+
+        elapsed
+    }
+
+    fn minutes(&self) -> u32 {
+        let (hour, minute) = self.time.split_once(':').unwrap_or_default();
+        hour.parse::<u32>().unwrap_or_default() * 60 + minute.parse::<u32>().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log(time: &str, stamp: Stamp) -> StampLog {
+        StampLog {
+            time: time.into(),
+            stamp,
+            group: None,
+            note: None,
+        }
+    }
+
+    #[test]
+    fn elapsed_while_resting() {
+        // Arrange
+        let logs = vec![
+            log("09:02", Stamp::ClockIn),
+            log("12:00", Stamp::StartBreak),
+        ];
+        let now = NaiveTime::from_hms_opt(12, 40, 0).unwrap();
+
+        // Act
+        let elapsed = StampLog::elapsed(&logs, now);
+
+        // Assert
+        assert!(elapsed.work == Duration::minutes(178));
+        assert!(elapsed.rest == Duration::minutes(40));
+    }
+
+    #[test]
+    fn elapsed_after_clock_out() {
+        // Arrange
+        let logs = vec![
+            log("09:00", Stamp::ClockIn),
+            log("12:00", Stamp::StartBreak),
+            log("13:00", Stamp::EndBreak),
+            log("18:00", Stamp::ClockOut),
+        ];
+        let now = NaiveTime::from_hms_opt(20, 0, 0).unwrap();
+
+        // Act
+        let elapsed = StampLog::elapsed(&logs, now);
+
+        // Assert
+        assert!(elapsed.work == Duration::hours(8));
+        assert!(elapsed.rest == Duration::hours(1));
+    }
+
+    #[test]
+    fn elapsed_of_night_shift_past_midnight() {
+        // Arrange
+        let logs = vec![log("22:00", Stamp::ClockIn)];
+        let now = NaiveTime::from_hms_opt(1, 30, 0).unwrap();
+
+        // Act
+        let elapsed = StampLog::elapsed(&logs, now);
+
+        // Assert
+        assert!(elapsed.work == Duration::minutes(210));
+    }
+}
@@ -1,6 +1,10 @@
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq, Eq)]
+use serde::Serialize;
+
+/// Working status of an employee, from `current_status` of the employee page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum WorkingStatus {
     Working,
     NotWorking,
@@ -16,6 +16,7 @@ fn setup() -> Setup {
 }
 
 #[tokio::test]
+#[ignore = "requires JOBCAN_EMAIL and JOBCAN_PASSWORD of a real account"]
 async fn process_of_getting_work_status_correctly() {
     let Setup {
         mut cmd,
@@ -32,6 +33,7 @@ async fn process_of_getting_work_status_correctly() {
 }
 
 #[tokio::test]
+#[ignore = "requires JOBCAN_EMAIL and JOBCAN_PASSWORD of a real account"]
 async fn process_of_getting_group_list_correctly() {
     let Setup {
         mut cmd,
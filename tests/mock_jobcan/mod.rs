@@ -0,0 +1,932 @@
+//! An in-process stand-in for Jobcan which serves just enough of the sign-in page, the
+//! employee page and the stamp endpoint to drive the CLI end to end.
+
+#![allow(dead_code)] // Note: Each test binary uses a different part of the mock
+
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use tiny_http::{Header, Method, Request, Response, Server};
+
+pub const EMAIL: &str = "user@example.com";
+pub const PASSWORD: &str = "password";
+
+const AUTHENTICITY_TOKEN: &str = "authenticity-token";
+const TOKEN: &str = "stamp-token";
+const FORM_TOKEN: &str = "form-token";
+const SESSION_COOKIE: &str = "_jobcan_session";
+
+/// A stamp request received by the mock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StampRequest {
+    pub adit_item: String,
+    pub group_id: String,
+    pub is_yakin: String,
+    pub notice: String,
+}
+
+/// A correction request received by the mock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CorrectionRequest {
+    pub year: String,
+    pub month: String,
+    pub day: String,
+    pub time: String,
+    pub group_id: String,
+    pub adit_item: String,
+    pub notice: String,
+}
+
+/// A leave application received by the mock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LeaveRequest {
+    pub year: String,
+    pub month: String,
+    pub day: String,
+    pub holiday_type_id: String,
+    pub half_day: String,
+    pub notice: String,
+}
+
+/// An overtime application received by the mock.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OvertimeRequest {
+    pub year: String,
+    pub month: String,
+    pub day: String,
+    pub end_time: String,
+    pub notice: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StampResponse {
+    /// Answer with the status after the transition, as Jobcan does.
+    Json,
+    /// Answer with the status before the stamp, as if the stamp was ignored.
+    Unchanged,
+    /// Answer with an HTML page instead of JSON.
+    Html,
+    /// Answer with the JSON but without a Content-Type header.
+    WithoutContentType,
+    /// Accept the stamp but answer 502 once, as if the response was lost on the way.
+    Lost,
+}
+
+struct State {
+    current_status: String,
+    groups: Vec<(String, String)>,
+    default_group_id: String,
+    stamp_response: StampResponse,
+    sessions: HashSet<String>,
+    login_count: usize,
+    employee_page_count: usize,
+    stamps: Vec<StampRequest>,
+    stamp_logs: Vec<[String; 4]>,
+    stamp_log_table: bool,
+    attendance: Vec<[String; 8]>,
+    attendance_query: Option<String>,
+    corrections: Vec<CorrectionRequest>,
+    approval_state: String,
+    leave_types: Vec<(String, String)>,
+    leave_balances: Vec<[String; 5]>,
+    leaves: Vec<LeaveRequest>,
+    overtime_rows: Vec<[String; 5]>,
+    overtimes: Vec<OvertimeRequest>,
+    overtime_query: Option<String>,
+    applications: Vec<[String; 5]>,
+    shifts: Vec<[String; 4]>,
+    shift_query: Option<String>,
+    failures: HashMap<String, usize>,
+    delay: Duration,
+    user_agent: Option<String>,
+    proxy_authorizations: Vec<Option<String>>,
+}
+
+pub struct MockJobcan {
+    server: Arc<Server>,
+    state: Arc<Mutex<State>>,
+    thread: Option<JoinHandle<()>>,
+    url: String,
+}
+
+impl MockJobcan {
+    pub fn start() -> MockJobcan {
+        let server = Arc::new(Server::http("127.0.0.1:0").expect("Failed to start mock server"));
+        let url = format!("http://{}", server.server_addr());
+        let state = Arc::new(Mutex::new(State {
+            current_status: "returned_home".into(),
+            groups: vec![
+                ("1".into(), "Head Office".into()),
+                ("2".into(), "Branch Office".into()),
+            ],
+            default_group_id: "1".into(),
+            stamp_response: StampResponse::Json,
+            sessions: HashSet::new(),
+            login_count: 0,
+            employee_page_count: 0,
+            stamps: Vec::new(),
+            stamp_logs: Vec::new(),
+            stamp_log_table: true,
+            attendance: Vec::new(),
+            attendance_query: None,
+            corrections: Vec::new(),
+            approval_state: "申請中".into(),
+            leave_types: vec![
+                ("1".into(), "有給休暇".into()),
+                ("2".into(), "特別休暇".into()),
+            ],
+            leave_balances: Vec::new(),
+            leaves: Vec::new(),
+            overtime_rows: Vec::new(),
+            overtimes: Vec::new(),
+            overtime_query: None,
+            applications: Vec::new(),
+            shifts: Vec::new(),
+            shift_query: None,
+            failures: HashMap::new(),
+            delay: Duration::ZERO,
+            user_agent: None,
+            proxy_authorizations: Vec::new(),
+        }));
+
+        let thread = {
+            let server = Arc::clone(&server);
+            let state = Arc::clone(&state);
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    handle(request, &state);
+                }
+            })
+        };
+
+        MockJobcan {
+            server,
+            state,
+            thread: Some(thread),
+            url,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn current_status(&self) -> String {
+        self.state.lock().unwrap().current_status.clone()
+    }
+
+    pub fn set_current_status(&self, status: &str) {
+        self.state.lock().unwrap().current_status = status.into();
+    }
+
+    /// Groups of the employee page as pairs of ID and name.
+    pub fn set_groups(&self, groups: Vec<(&str, &str)>) {
+        self.state.lock().unwrap().groups = groups
+            .into_iter()
+            .map(|(id, name)| (id.into(), name.into()))
+            .collect();
+    }
+
+    pub fn set_stamp_response(&self, response: StampResponse) {
+        self.state.lock().unwrap().stamp_response = response;
+    }
+
+    pub fn login_count(&self) -> usize {
+        self.state.lock().unwrap().login_count
+    }
+
+    /// Times the employee page was served to a signed-in client.
+    pub fn employee_page_count(&self) -> usize {
+        self.state.lock().unwrap().employee_page_count
+    }
+
+    pub fn stamps(&self) -> Vec<StampRequest> {
+        self.state.lock().unwrap().stamps.clone()
+    }
+
+    /// Rows of today's stamp log: time, stamp type, group and note.
+    pub fn set_stamp_logs(&self, rows: Vec<[&str; 4]>) {
+        self.state.lock().unwrap().stamp_logs =
+            rows.into_iter().map(|row| row.map(String::from)).collect();
+    }
+
+    /// Leave the stamp log table out of the employee page, as Jobcan does for some accounts.
+    pub fn hide_stamp_log_table(&self) {
+        self.state.lock().unwrap().stamp_log_table = false;
+    }
+
+    /// Rows of the attendance table: date, holiday type, clock-in, clock-out, work time,
+    /// overtime, break time and notes.
+    pub fn set_attendance(&self, rows: Vec<[&str; 8]>) {
+        self.state.lock().unwrap().attendance =
+            rows.into_iter().map(|row| row.map(String::from)).collect();
+    }
+
+    /// Answer the next `count` requests to `path` with 503, before handling them.
+    pub fn fail_requests(&self, path: &str, count: usize) {
+        self.state
+            .lock()
+            .unwrap()
+            .failures
+            .insert(path.into(), count);
+    }
+
+    /// Wait for `delay` before answering each request.
+    pub fn set_delay(&self, delay: Duration) {
+        self.state.lock().unwrap().delay = delay;
+    }
+
+    /// `User-Agent` header of the last request.
+    pub fn user_agent(&self) -> Option<String> {
+        self.state.lock().unwrap().user_agent.clone()
+    }
+
+    /// `Proxy-Authorization` headers of the requests received as a proxy, i.e. with an
+    /// absolute URL.
+    pub fn proxy_authorizations(&self) -> Vec<Option<String>> {
+        self.state.lock().unwrap().proxy_authorizations.clone()
+    }
+
+    pub fn corrections(&self) -> Vec<CorrectionRequest> {
+        self.state.lock().unwrap().corrections.clone()
+    }
+
+    /// State label shown for every application, e.g. `申請中`, or empty if no approval
+    /// is required.
+    pub fn set_approval_state(&self, label: &str) {
+        self.state.lock().unwrap().approval_state = label.into();
+    }
+
+    /// Rows of the leave balance table: leave type, granted, used, remaining and expiry.
+    pub fn set_leave_balances(&self, rows: Vec<[&str; 5]>) {
+        self.state.lock().unwrap().leave_balances =
+            rows.into_iter().map(|row| row.map(String::from)).collect();
+    }
+
+    pub fn leaves(&self) -> Vec<LeaveRequest> {
+        self.state.lock().unwrap().leaves.clone()
+    }
+
+    /// Rows of the overtime page listed before the applications received: date, end time,
+    /// overtime, reason and state.
+    pub fn set_overtime_rows(&self, rows: Vec<[&str; 5]>) {
+        self.state.lock().unwrap().overtime_rows =
+            rows.into_iter().map(|row| row.map(String::from)).collect();
+    }
+
+    pub fn overtimes(&self) -> Vec<OvertimeRequest> {
+        self.state.lock().unwrap().overtimes.clone()
+    }
+
+    /// Query string of the last overtime page request.
+    pub fn overtime_query(&self) -> Option<String> {
+        self.state.lock().unwrap().overtime_query.clone()
+    }
+
+    /// Rows of the application history: type, date, submitted at, approver and state.
+    pub fn set_applications(&self, rows: Vec<[&str; 5]>) {
+        self.state.lock().unwrap().applications =
+            rows.into_iter().map(|row| row.map(String::from)).collect();
+    }
+
+    /// Rows of the shift page: date, planned start, planned end and group.
+    pub fn set_shifts(&self, rows: Vec<[&str; 4]>) {
+        self.state.lock().unwrap().shifts =
+            rows.into_iter().map(|row| row.map(String::from)).collect();
+    }
+
+    /// Query string of the last shift page request.
+    pub fn shift_query(&self) -> Option<String> {
+        self.state.lock().unwrap().shift_query.clone()
+    }
+
+    /// Query string of the last attendance page request.
+    pub fn attendance_query(&self) -> Option<String> {
+        self.state.lock().unwrap().attendance_query.clone()
+    }
+}
+
+impl Drop for MockJobcan {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+fn handle(mut request: Request, state: &Mutex<State>) {
+    let mut body = String::new();
+    let _ = request.as_reader().read_to_string(&mut body);
+    let form: Vec<(String, String)> = url::form_urlencoded::parse(body.as_bytes())
+        .into_owned()
+        .collect();
+    let field = |name: &str| {
+        form.iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_default()
+    };
+
+    let delay = state.lock().unwrap().delay;
+    thread::sleep(delay);
+
+    let mut state = state.lock().unwrap();
+    state.user_agent = header_of(&request, "User-Agent");
+    // Note: A request through a proxy has an absolute URL, which the mock serves as if it
+    // were the origin
+    let url = match request.url().strip_prefix("http://") {
+        Some(rest) => {
+            let authorization = header_of(&request, "Proxy-Authorization");
+            state.proxy_authorizations.push(authorization);
+            rest.find('/').map_or("/", |i| &rest[i..]).to_string()
+        }
+        None => request.url().to_string(),
+    };
+    let session = session_of(&request);
+    let signed_in = session.is_some_and(|s| state.sessions.contains(&s));
+    let (path, query) = match url.split_once('?') {
+        Some((path, query)) => (path.to_string(), Some(query.to_string())),
+        None => (url, None),
+    };
+
+    if let Some(count) = state.failures.get_mut(&path).filter(|count| **count > 0) {
+        *count -= 1;
+        let _ = request.respond(Response::from_string("").with_status_code(503));
+        return;
+    }
+
+    let response = match (request.method(), path.as_str()) {
+        (Method::Get, "/users/sign_in") => html(sign_in_page()),
+        (Method::Post, "/users/sign_in") => {
+            if field("authenticity_token") == AUTHENTICITY_TOKEN
+                && field("user[email]") == EMAIL
+                && field("user[password]") == PASSWORD
+            {
+                state.login_count += 1;
+                let session = format!("session-{}", state.login_count);
+                state.sessions.insert(session.clone());
+                redirect("/employee").with_header(header(
+                    "Set-Cookie",
+                    &format!("{}={}; Path=/; HttpOnly", SESSION_COOKIE, session),
+                ))
+            } else {
+                html(sign_in_page())
+            }
+        }
+        (Method::Get, "/employee") if signed_in => {
+            state.employee_page_count += 1;
+            html(employee_page(&state))
+        }
+        (Method::Post, "/employee/index/adit") if signed_in && field("token") == TOKEN => {
+            stamp(&mut state, field)
+        }
+        (Method::Get, "/employee/attendance") if signed_in => {
+            state.attendance_query = query;
+            html(attendance_page(&state))
+        }
+        (Method::Get, "/employee/adit/modify/") if signed_in => {
+            let query: Vec<(String, String)> =
+                url::form_urlencoded::parse(query.unwrap_or_default().as_bytes())
+                    .into_owned()
+                    .collect();
+            let value = |name: &str| {
+                query
+                    .iter()
+                    .find(|(key, _)| key == name)
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default()
+            };
+            html(correction_page(
+                &state,
+                [value("year"), value("month"), value("day")],
+                None,
+            ))
+        }
+        (Method::Post, "/employee/adit/insert/") if signed_in && field("token") == FORM_TOKEN => {
+            correct(&mut state, field)
+        }
+        (Method::Get, "/employee/over-work/") if signed_in => {
+            state.overtime_query = query;
+            html(overtime_page(&state))
+        }
+        (Method::Get, "/employee/over-work/new") if signed_in => html(overtime_form(None)),
+        (Method::Post, "/employee/over-work/create")
+            if signed_in && field("token") == FORM_TOKEN =>
+        {
+            request_overtime(&mut state, field)
+        }
+        (Method::Get, "/employee/request/") if signed_in => html(application_page(&state)),
+        (Method::Get, "/employee/shift/") if signed_in => {
+            state.shift_query = query;
+            html(shift_page(&state))
+        }
+        (Method::Get, "/employee/holiday/") if signed_in => html(leave_page(&state)),
+        (Method::Get, "/employee/holiday/new") if signed_in => html(leave_form(&state, None)),
+        (Method::Post, "/employee/holiday/create") if signed_in && field("token") == FORM_TOKEN => {
+            request_leave(&mut state, field)
+        }
+        (_, path) if path.starts_with("/employee") => redirect("/users/sign_in"),
+        _ => Response::from_string("Not Found").with_status_code(404),
+    };
+
+    let _ = request.respond(response);
+}
+
+fn stamp(state: &mut State, field: impl Fn(&str) -> String) -> Response<std::io::Cursor<Vec<u8>>> {
+    let adit_item = field("adit_item");
+    state.stamps.push(StampRequest {
+        adit_item: adit_item.clone(),
+        group_id: field("adit_group_id"),
+        is_yakin: field("is_yakin"),
+        notice: field("notice"),
+    });
+
+    let previous = state.current_status.clone();
+    let next = match (state.current_status.as_str(), adit_item.as_str()) {
+        ("returned_home" | "having_breakfast", "work_start") => "working",
+        ("working", "work_end") => "returned_home",
+        ("working", "rest_start") => "resting",
+        ("resting", "rest_end") => "working",
+        (current, _) => current,
+    };
+    state.current_status = next.to_string();
+
+    match state.stamp_response {
+        StampResponse::Json => Response::from_string(format!(
+            r#"{{"result":1,"state":1,"current_status":"{}"}}"#,
+            state.current_status
+        ))
+        .with_header(header("Content-Type", "application/json")),
+        StampResponse::Unchanged => Response::from_string(format!(
+            r#"{{"result":1,"state":1,"current_status":"{}"}}"#,
+            previous
+        ))
+        .with_header(header("Content-Type", "application/json")),
+        StampResponse::Html => html("<html><body>Error</body></html>".into()),
+        StampResponse::WithoutContentType => Response::from_data(format!(
+            r#"{{"result":1,"state":1,"current_status":"{}"}}"#,
+            state.current_status
+        )),
+        StampResponse::Lost => {
+            state.stamp_response = StampResponse::Json;
+            Response::from_string("").with_status_code(502)
+        }
+    }
+}
+
+fn correct(
+    state: &mut State,
+    field: impl Fn(&str) -> String,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let date = [field("year"), field("month"), field("day")];
+    if field("notice").is_empty() {
+        return html(correction_page(
+            state,
+            date,
+            Some("申請理由を入力してください"),
+        ));
+    }
+
+    state.corrections.push(CorrectionRequest {
+        year: field("year"),
+        month: field("month"),
+        day: field("day"),
+        time: field("time"),
+        group_id: field("group_id"),
+        adit_item: field("adit_item"),
+        notice: field("notice"),
+    });
+    let [year, month, day] = date;
+    redirect(&format!(
+        "/employee/adit/modify/?year={}&month={}&day={}",
+        year, month, day
+    ))
+}
+
+fn request_leave(
+    state: &mut State,
+    field: impl Fn(&str) -> String,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    let holiday_type_id = field("holiday_type_id");
+    if !state
+        .leave_types
+        .iter()
+        .any(|(id, _)| *id == holiday_type_id)
+    {
+        return html(leave_form(state, Some("休暇種別を選択してください")));
+    }
+
+    state.leaves.push(LeaveRequest {
+        year: field("year"),
+        month: field("month"),
+        day: field("day"),
+        holiday_type_id,
+        half_day: field("half_day"),
+        notice: field("notice"),
+    });
+    redirect("/employee/holiday/")
+}
+
+fn request_overtime(
+    state: &mut State,
+    field: impl Fn(&str) -> String,
+) -> Response<std::io::Cursor<Vec<u8>>> {
+    if field("notice").is_empty() {
+        return html(overtime_form(Some("申請理由を入力してください")));
+    }
+
+    state.overtimes.push(OvertimeRequest {
+        year: field("year"),
+        month: field("month"),
+        day: field("day"),
+        end_time: field("end_time"),
+        notice: field("notice"),
+    });
+    redirect(&format!(
+        "/employee/over-work/?year={}&month={}",
+        field("year"),
+        field("month")
+    ))
+}
+
+fn session_of(request: &Request) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .filter(|h| h.field.equiv("Cookie"))
+        .flat_map(|h| h.value.as_str().split("; "))
+        .find_map(|cookie| cookie.strip_prefix(&format!("{}=", SESSION_COOKIE)))
+        .map(String::from)
+}
+
+fn header_of(request: &Request, field: &'static str) -> Option<String> {
+    request
+        .headers()
+        .iter()
+        .find(|h| h.field.equiv(field))
+        .map(|h| h.value.to_string())
+}
+
+fn sign_in_page() -> String {
+    format!(
+        r#"<html>
+            <body>
+                <form action="/users/sign_in" method="post">
+                    <input type="hidden" name="authenticity_token" value="{}">
+                    <input type="email" name="user[email]">
+                    <input type="password" name="user[password]">
+                </form>
+            </body>
+        </html>"#,
+        AUTHENTICITY_TOKEN
+    )
+}
+
+fn employee_page(state: &State) -> String {
+    let options: String = state
+        .groups
+        .iter()
+        .map(|(id, name)| format!(r#"<option value="{}">{}</option>"#, id, name))
+        .collect();
+    let logs = if state.stamp_log_table {
+        format!(
+            r#"<table class="table jbc-table">
+                <thead>
+                    <tr>
+                        <th>時刻</th>
+                        <th>打刻区分</th>
+                        <th>グループ</th>
+                        <th>備考</th>
+                    </tr>
+                </thead>
+                <tbody>{}</tbody>
+            </table>"#,
+            table_rows(&state.stamp_logs)
+        )
+    } else {
+        String::new()
+    };
+    format!(
+        r#"<html>
+            <head>
+                <script>
+                    var current_status = "{}";
+                    var defaultAditGroupId = {};
+                </script>
+            </head>
+            <body>
+                <input type="hidden" name="token" value="{}">
+                <select id="adit_group_id">{}</select>
+                {}
+            </body>
+        </html>"#,
+        state.current_status, state.default_group_id, TOKEN, options, logs
+    )
+}
+
+/// The correction form of `date` as year, month and day, listing the requests of the day.
+fn correction_page(state: &State, date: [String; 3], alert: Option<&str>) -> String {
+    let options: String = state
+        .groups
+        .iter()
+        .map(|(id, name)| format!(r#"<option value="{}">{}</option>"#, id, name))
+        .collect();
+    let rows: Vec<[String; 5]> = state
+        .corrections
+        .iter()
+        .filter(|c| [&c.year, &c.month, &c.day] == [&date[0], &date[1], &date[2]])
+        .map(|c| {
+            let label = match c.adit_item.as_str() {
+                "work_start" => "出勤",
+                "work_end" => "退勤",
+                "rest_start" => "休憩開始",
+                "rest_end" => "休憩終了",
+                _ => "",
+            };
+            let group = state
+                .groups
+                .iter()
+                .find(|(id, _)| *id == c.group_id)
+                .map(|(_, name)| name.clone())
+                .unwrap_or_default();
+            [
+                format!("{}:{}", &c.time[..2], &c.time[2..]),
+                label.into(),
+                group,
+                c.notice.clone(),
+                state.approval_state.clone(),
+            ]
+        })
+        .collect();
+    let alert = alert
+        .map(|message| format!(r#"<div class="alert alert-danger">{}</div>"#, message))
+        .unwrap_or_default();
+    format!(
+        r#"<html>
+            <body>
+                {}
+                <form action="/employee/adit/insert/" method="post">
+                    <input type="hidden" name="token" value="{}">
+                    <select name="group_id">{}</select>
+                </form>
+                <table class="table jbc-table">
+                    <thead>
+                        <tr>
+                            <th>時刻</th>
+                            <th>打刻区分</th>
+                            <th>グループ</th>
+                            <th>申請理由</th>
+                            <th>状態</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+            </body>
+        </html>"#,
+        alert,
+        FORM_TOKEN,
+        options,
+        table_rows(&rows)
+    )
+}
+
+fn overtime_form(alert: Option<&str>) -> String {
+    let alert = alert
+        .map(|message| format!(r#"<div class="alert alert-danger">{}</div>"#, message))
+        .unwrap_or_default();
+    format!(
+        r#"<html>
+            <body>
+                {}
+                <form action="/employee/over-work/create" method="post">
+                    <input type="hidden" name="token" value="{}">
+                </form>
+            </body>
+        </html>"#,
+        alert, FORM_TOKEN
+    )
+}
+
+/// The overtime page with the rows set by the test followed by the applications received.
+fn overtime_page(state: &State) -> String {
+    let received = state.overtimes.iter().map(|overtime| {
+        [
+            format!(
+                "{}/{:0>2}/{:0>2}",
+                overtime.year, overtime.month, overtime.day
+            ),
+            format!("{}:{}", &overtime.end_time[..2], &overtime.end_time[2..]),
+            String::new(),
+            overtime.notice.clone(),
+            state.approval_state.clone(),
+        ]
+    });
+    let rows: Vec<[String; 5]> = state
+        .overtime_rows
+        .iter()
+        .cloned()
+        .chain(received)
+        .collect();
+    format!(
+        r#"<html>
+            <body>
+                <table class="table jbc-table">
+                    <thead>
+                        <tr>
+                            <th>対象日</th>
+                            <th>終了予定時刻</th>
+                            <th>残業時間</th>
+                            <th>申請理由</th>
+                            <th>状態</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+            </body>
+        </html>"#,
+        table_rows(&rows)
+    )
+}
+
+fn leave_form(state: &State, alert: Option<&str>) -> String {
+    let options: String = state
+        .leave_types
+        .iter()
+        .map(|(id, name)| format!(r#"<option value="{}">{}</option>"#, id, name))
+        .collect();
+    let alert = alert
+        .map(|message| format!(r#"<div class="alert alert-danger">{}</div>"#, message))
+        .unwrap_or_default();
+    format!(
+        r#"<html>
+            <body>
+                {}
+                <form action="/employee/holiday/create" method="post">
+                    <input type="hidden" name="token" value="{}">
+                    <select name="holiday_type_id">
+                        <option value="">選択してください</option>
+                        {}
+                    </select>
+                </form>
+            </body>
+        </html>"#,
+        alert, FORM_TOKEN, options
+    )
+}
+
+/// The leave page with the balance table and the applications received.
+fn leave_page(state: &State) -> String {
+    let applications: Vec<[String; 5]> = state
+        .leaves
+        .iter()
+        .map(|leave| {
+            let leave_type = state
+                .leave_types
+                .iter()
+                .find(|(id, _)| *id == leave.holiday_type_id)
+                .map(|(_, name)| name.clone())
+                .unwrap_or_default();
+            let period = match leave.half_day.as_str() {
+                "1" => "午前休",
+                "2" => "午後休",
+                _ => "全日",
+            };
+            [
+                format!("{}/{:0>2}/{:0>2}", leave.year, leave.month, leave.day),
+                leave_type,
+                period.into(),
+                leave.notice.clone(),
+                state.approval_state.clone(),
+            ]
+        })
+        .collect();
+    format!(
+        r#"<html>
+            <body>
+                <table class="table jbc-table">
+                    <thead>
+                        <tr>
+                            <th>休暇種別</th>
+                            <th>付与日数</th>
+                            <th>取得日数</th>
+                            <th>残日数</th>
+                            <th>有効期限</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+                <table class="table jbc-table">
+                    <thead>
+                        <tr>
+                            <th>取得日</th>
+                            <th>休暇種別</th>
+                            <th>区分</th>
+                            <th>申請理由</th>
+                            <th>状態</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+            </body>
+        </html>"#,
+        table_rows(&state.leave_balances),
+        table_rows(&applications)
+    )
+}
+
+fn application_page(state: &State) -> String {
+    format!(
+        r#"<html>
+            <body>
+                <table class="table jbc-table">
+                    <thead>
+                        <tr>
+                            <th>申請種別</th>
+                            <th>対象日</th>
+                            <th>申請日時</th>
+                            <th>承認者</th>
+                            <th>状態</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+            </body>
+        </html>"#,
+        table_rows(&state.applications)
+    )
+}
+
+fn shift_page(state: &State) -> String {
+    format!(
+        r#"<html>
+            <body>
+                <table class="table jbc-table">
+                    <thead>
+                        <tr>
+                            <th>日付</th>
+                            <th>開始予定</th>
+                            <th>終了予定</th>
+                            <th>グループ</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+            </body>
+        </html>"#,
+        table_rows(&state.shifts)
+    )
+}
+
+fn attendance_page(state: &State) -> String {
+    let rows = table_rows(&state.attendance);
+    format!(
+        r#"<html>
+            <body>
+                <table class="table jbc-table">
+                    <thead>
+                        <tr>
+                            <th>日付</th>
+                            <th>休日区分</th>
+                            <th>出勤時刻</th>
+                            <th>退勤時刻</th>
+                            <th>労働時間</th>
+                            <th>残業</th>
+                            <th>休憩時間</th>
+                            <th>備考</th>
+                        </tr>
+                    </thead>
+                    <tbody>{}</tbody>
+                </table>
+            </body>
+        </html>"#,
+        rows
+    )
+}
+
+fn table_rows<const N: usize>(rows: &[[String; N]]) -> String {
+    rows.iter()
+        .map(|row| {
+            let cells: String = row.iter().map(|c| format!("<td>{}</td>", c)).collect();
+            format!("<tr>{}</tr>", cells)
+        })
+        .collect()
+}
+
+fn html(body: String) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string(body).with_header(header("Content-Type", "text/html; charset=utf-8"))
+}
+
+fn redirect(location: &str) -> Response<std::io::Cursor<Vec<u8>>> {
+    Response::from_string("")
+        .with_status_code(302)
+        .with_header(header("Location", location))
+}
+
+fn header(field: &str, value: &str) -> Header {
+    Header::from_bytes(field, value).unwrap()
+}